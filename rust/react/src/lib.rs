@@ -1,14 +1,28 @@
-use std::{collections::HashMap, ops::Deref};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, BTreeSet, HashMap, VecDeque},
+    fmt::{self, Write},
+    ops::{Add, Deref, DerefMut, Div},
+    rc::{Rc, Weak},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+// Every reactor gets a distinct id, which is stamped into the ids of its cells so that an id from
+// one reactor is never mistaken for a cell of another.
+static NEXT_REACTOR_ID: AtomicUsize = AtomicUsize::new(0);
 
 /// `InputCellId` is a unique identifier for an input cell.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct InputCellId(usize);
+pub struct InputCellId {
+    reactor: usize,
+    id: usize,
+}
 
 impl Deref for InputCellId {
     type Target = usize;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.id
     }
 }
 
@@ -27,16 +41,19 @@ impl Deref for InputCellId {
 /// let compute: react::InputCellId = r.create_compute(&[react::CellId::Input(input)], |_| 222).unwrap();
 /// ```
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
-pub struct ComputeCellId(usize);
+pub struct ComputeCellId {
+    reactor: usize,
+    id: usize,
+}
 impl Deref for ComputeCellId {
     type Target = usize;
 
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.id
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct CallbackId(usize);
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -52,6 +69,22 @@ impl CellId {
             CellId::Compute(cell_id) => *cell_id.deref(),
         }
     }
+
+    fn get_reactor_id(&self) -> usize {
+        match self {
+            CellId::Input(cell_id) => cell_id.reactor,
+            CellId::Compute(cell_id) => cell_id.reactor,
+        }
+    }
+}
+
+/// A dependency of a compute cell created by `Reactor::create_compute_many`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BatchDep {
+    // A cell that already exists in the reactor.
+    Cell(CellId),
+    // The cell created by the spec at this index, which must come earlier in the same batch.
+    Earlier(usize),
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -60,13 +93,74 @@ pub enum RemoveCallbackError {
     NonexistentCallback,
 }
 
+/// What `Reactor::inspect` knows about a cell.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CellStatus<T> {
+    Missing,
+    Input(T),
+    // A compute cell is dirty when its cached value is out of date with its dependencies, which
+    // happens while the cell is frozen or overridden.
+    Compute { value: T, dirty: bool },
+}
+
+/// `ReactorState` holds the values of a set of cells at one point in time.
+///
+/// Compute functions are not captured, only the values the cells held.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReactorState<T> {
+    inputs: Vec<(InputCellId, T)>,
+    computes: Vec<(ComputeCellId, T)>,
+}
+
+impl<T> ReactorState<T> {
+    // Returns the captured input cells and their values, in the order they were created.
+    pub fn inputs(&self) -> &[(InputCellId, T)] {
+        &self.inputs
+    }
+
+    // Returns the captured compute cells and their values, in the order they were created.
+    pub fn computes(&self) -> &[(ComputeCellId, T)] {
+        &self.computes
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CreateComputeError {
+    NonexistentDependency(CellId),
+    // More dependencies than allowed by `Reactor::set_max_fan_in`.
+    TooManyDependencies { limit: usize, got: usize },
+    // `Reactor::create_average` was given no dependencies.
+    EmptyDependencies,
+    // `Reactor::create_average` was given more dependencies than it can count, i.e. more than
+    // `u16::MAX`.
+    TooManyToAverage { got: usize },
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SetValueError {
+    NonexistentCell,
+    NotFinite,
+}
+
 struct InputCell<T>(T);
 
-type ComputeFn<'a, T> = Box<dyn 'a + Fn(&[T]) -> T>;
+pub type ComputeFn<'a, T> = Box<dyn 'a + Fn(&[T]) -> T>;
 struct ComputeCell<'a, T> {
     dependencies: Vec<CellId>,
     func: ComputeFn<'a, T>,
     value: T,
+    frozen: bool,
+    // Whether `value` was forced by `Reactor::override_value`.
+    overridden: bool,
+    // The length of the longest path from an input cell to this cell.
+    depth: usize,
+}
+
+impl<'a, T> ComputeCell<'a, T> {
+    // A frozen or overridden cell keeps its value when its dependencies change.
+    fn is_pinned(&self) -> bool {
+        self.frozen || self.overridden
+    }
 }
 
 enum Cell<'a, T> {
@@ -78,66 +172,289 @@ impl<'a, T> Cell<'a, T>
 where
     T: Copy + PartialEq,
 {
-    fn get_value(&self, reactor: &Reactor<T>) -> T {
+    // Compute cells cache their value, which is kept up to date as their dependencies change
+    // (unless the cell is frozen or overridden).
+    fn get_value(&self) -> T {
         match self {
             Cell::Input(input_cell) => input_cell.0,
-            Cell::Compute(compute_cell) => {
-                let init_vec: Vec<T> = Vec::new();
-                let input_vec =
-                    compute_cell
-                        .dependencies
-                        .iter()
-                        .fold(init_vec, |new_vec, cell_id| {
-                            let cells = match cell_id {
-                                CellId::Input(_) => &reactor.input_cells,
-                                CellId::Compute(_) => &reactor.compute_cells,
-                            };
-                            let input = cells
-                                .get(&cell_id.get_id())
-                                .map(|v| v.get_value(reactor))
-                                .into_iter()
-                                .collect::<Vec<_>>();
-                            [new_vec, input].concat()
-                        });
-                let func = &compute_cell.func;
-                func(&input_vec)
-            }
+            Cell::Compute(compute_cell) => compute_cell.value,
         }
     }
 }
 
+// A registered callback returns whether it should stay registered.
+type CallbackFn<'a, T> = Box<dyn 'a + FnMut(T) -> bool>;
 struct CallbackEntry<'a, T> {
     id: usize,
-    callbacks: HashMap<CallbackId, Box<dyn 'a + FnMut(T)>>,
+    // Callback ids increase with every registration, so the callbacks are kept (and run) in the
+    // order they were added.
+    callbacks: BTreeMap<CallbackId, CallbackFn<'a, T>>,
+    // The tags of the callbacks that were added with one.
+    tags: HashMap<CallbackId, u64>,
+    // Whether each weakly held callback is still alive.
+    weak_probes: HashMap<CallbackId, Box<dyn 'a + Fn() -> bool>>,
+}
+
+impl<'a, T> CallbackEntry<'a, T> {
+    // Returns the ids of the callbacks that can still be called, in the order they were added,
+    // leaving out weak callbacks whose owner dropped them but that have not been pruned yet.
+    fn live_ids(&self) -> impl Iterator<Item = CallbackId> + '_ {
+        self.callbacks
+            .keys()
+            .copied()
+            .filter(|id| self.weak_probes.get(id).is_none_or(|alive| alive()))
+    }
+}
+
+/// `InputGuard` gives mutable access to the value of an input cell.
+///
+/// Edits are applied to a local copy; when the guard is dropped the final value is written back
+/// with a single `set_value`, so any number of edits results in at most one propagation.
+pub struct InputGuard<'r, 'a, T: Copy + PartialEq + 'a> {
+    reactor: &'r mut Reactor<'a, T>,
+    id: InputCellId,
+    value: T,
+    modified: bool,
+}
+
+impl<'r, 'a, T: Copy + PartialEq + 'a> Deref for InputGuard<'r, 'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<'r, 'a, T: Copy + PartialEq + 'a> DerefMut for InputGuard<'r, 'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.modified = true;
+        &mut self.value
+    }
+}
+
+impl<'r, 'a, T: Copy + PartialEq + 'a> Drop for InputGuard<'r, 'a, T> {
+    fn drop(&mut self) {
+        if self.modified {
+            self.reactor.set_value(self.id, self.value);
+        }
+    }
+}
+
+/// `DeferredWriter` lets callbacks write to input cells of the reactor that runs them.
+///
+/// A callback cannot borrow the reactor, so its writes are queued instead, and applied once the
+/// propagation that ran the callback has finished. Writes queued outside of a callback wait until
+/// the next propagation, or until `Reactor::apply_deferred_writes` is called.
+///
+/// A writer outlives a `Reactor::merge` of its reactor into another one: its writes then go to
+/// the imported input cells of the merged reactor.
+///
+/// Writes to cells the reactor doesn't have, such as cells of another reactor, are ignored.
+#[derive(Clone)]
+pub struct DeferredWriter<T> {
+    queue: WriteQueue<T>,
+}
+
+impl<T> DeferredWriter<T> {
+    pub fn set_value(&self, id: InputCellId, new_value: T) {
+        self.queue.borrow_mut().push((id, new_value));
+    }
 }
 
+type WriteQueue<T> = Rc<RefCell<Vec<(InputCellId, T)>>>;
+
+// Decides whether a recomputed value is the same as the cached one, i.e. whether the cell changed.
+type ValueEqFn<'a, T> = Box<dyn 'a + Fn(&T, &T) -> bool>;
+
 pub struct Reactor<'a, T> {
+    reactor_id: usize,
     id: usize,
     input_cells: HashMap<usize, Cell<'a, T>>,
     compute_cells: HashMap<usize, Cell<'a, T>>,
     callbacks: HashMap<ComputeCellId, CallbackEntry<'a, T>>,
     dependencies: HashMap<CellId, Vec<CellId>>,
+    record: bool,
+    event_log: Vec<(InputCellId, T)>,
+    value_eq: ValueEqFn<'a, T>,
+    // How many times each compute cell was recomputed, while profiling is enabled.
+    recompute_counts: Option<HashMap<usize, u64>>,
+    max_fan_in: Option<usize>,
+    // The most recent `set_value` operations, at most `recent_capacity` of them, oldest first.
+    recent_capacity: Option<usize>,
+    recent_log: VecDeque<(InputCellId, T)>,
+    // Writes queued by `DeferredWriter`s, and whether they are being applied already.
+    deferred_writes: WriteQueue<T>,
+    propagating: bool,
+    // The write queues of the reactors merged into this one, each with the new ids of the input
+    // cells its writers refer to.
+    merged_writes: Vec<(WriteQueue<T>, HashMap<InputCellId, InputCellId>)>,
 }
 
 impl<'a, T: Copy + PartialEq> Default for Reactor<'a, T> {
     fn default() -> Self {
+        let reactor_id = NEXT_REACTOR_ID.fetch_add(1, Ordering::Relaxed);
         let id = 0;
         let input_cells = HashMap::new();
         let compute_cells = HashMap::new();
         let callbacks = HashMap::new();
         let dependencies = HashMap::new();
+        let record = false;
+        let event_log = Vec::new();
+        let value_eq = Box::new(|a: &T, b: &T| a == b);
+        let recompute_counts = None;
+        let max_fan_in = None;
+        let recent_capacity = None;
+        let recent_log = VecDeque::new();
+        let deferred_writes = Rc::new(RefCell::new(Vec::new()));
+        let propagating = false;
+        let merged_writes = Vec::new();
         Self {
+            reactor_id,
             id,
             input_cells,
             compute_cells,
             callbacks,
             dependencies,
+            record,
+            event_log,
+            value_eq,
+            recompute_counts,
+            max_fan_in,
+            recent_capacity,
+            recent_log,
+            deferred_writes,
+            propagating,
+            merged_writes,
+        }
+    }
+}
+
+/// Floating point values that can be compared within an epsilon.
+pub trait Float: Copy + PartialEq {
+    /// Returns the absolute difference between `self` and `other`, widened to f64.
+    fn distance(self, other: Self) -> f64;
+
+    /// Returns whether the value is neither infinite nor NaN.
+    fn is_finite(self) -> bool;
+}
+
+impl Float for f64 {
+    fn distance(self, other: Self) -> f64 {
+        (self - other).abs()
+    }
+
+    fn is_finite(self) -> bool {
+        f64::is_finite(self)
+    }
+}
+
+impl Float for f32 {
+    fn distance(self, other: Self) -> f64 {
+        (self - other).abs() as f64
+    }
+
+    fn is_finite(self) -> bool {
+        f32::is_finite(self)
+    }
+}
+
+impl<'a, T: Float + 'a> Reactor<'a, T> {
+    // Creates a reactor that considers a compute cell unchanged, and so does not run its
+    // callbacks, while its recomputed value stays within `epsilon` of the current one.
+    //
+    // An unchanged cell keeps its current value, so it may lag behind its exact value by up to
+    // `epsilon`, and so may its dependants. Changes too small to propagate still add up: once the
+    // exact value is more than `epsilon` away from the current one, the cell changes.
+    pub fn with_float_epsilon(epsilon: f64) -> Self {
+        ReactorBuilder::new().with_float_epsilon(epsilon).build()
+    }
+
+    // Sets the value of the specified input cell, like `set_value`, but refuses NaN and infinite
+    // values, which would make every downstream cell look changed on each propagation.
+    pub fn set_value_checked(
+        &mut self,
+        id: InputCellId,
+        new_value: T,
+    ) -> Result<(), SetValueError> {
+        if !new_value.is_finite() {
+            return Err(SetValueError::NotFinite);
+        }
+        if !self.set_value(id, new_value) {
+            return Err(SetValueError::NonexistentCell);
+        }
+        Ok(())
+    }
+}
+
+/// Configures a `Reactor` before any cell is created.
+pub struct ReactorBuilder<'a, T> {
+    profiling: bool,
+    record: bool,
+    recent_capacity: Option<usize>,
+    value_eq: Option<ValueEqFn<'a, T>>,
+}
+
+impl<'a, T: Copy + PartialEq + 'a> Default for ReactorBuilder<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T: Copy + PartialEq + 'a> ReactorBuilder<'a, T> {
+    pub fn new() -> Self {
+        ReactorBuilder {
+            profiling: false,
+            record: false,
+            recent_capacity: None,
+            value_eq: None,
+        }
+    }
+
+    // Enables counting of compute cell recomputations, as with `Reactor::enable_profiling`.
+    pub fn with_profiling(mut self) -> Self {
+        self.profiling = true;
+        self
+    }
+
+    // Enables recording of `set_value` operations, as with `Reactor::set_recording`.
+    pub fn with_recording(mut self) -> Self {
+        self.record = true;
+        self
+    }
+
+    // Keeps the `capacity` most recent `set_value` operations, as with
+    // `Reactor::enable_recent_log`.
+    pub fn with_recent_log(mut self, capacity: usize) -> Self {
+        self.recent_capacity = Some(capacity);
+        self
+    }
+
+    pub fn build(self) -> Reactor<'a, T> {
+        let mut reactor = Reactor::default();
+        if self.profiling {
+            reactor.enable_profiling();
+        }
+        reactor.set_recording(self.record);
+        if let Some(capacity) = self.recent_capacity {
+            reactor.enable_recent_log(capacity);
         }
+        if let Some(value_eq) = self.value_eq {
+            reactor.value_eq = value_eq;
+        }
+        reactor
+    }
+}
+
+impl<'a, T: Float + 'a> ReactorBuilder<'a, T> {
+    // Considers a compute cell unchanged while its recomputed value stays within `epsilon` of
+    // the current one, as with `Reactor::with_float_epsilon`.
+    pub fn with_float_epsilon(mut self, epsilon: f64) -> Self {
+        self.value_eq = Some(Box::new(move |a: &T, b: &T| a.distance(*b) <= epsilon));
+        self
     }
 }
 
-// You are guaranteed that Reactor will only be tested against types that are Copy + PartialEq.
-impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
+impl<'a, T: Copy + PartialEq + 'a> Reactor<'a, T> {
     pub fn new() -> Self {
         Reactor::default()
     }
@@ -145,7 +462,10 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
     // Creates an input cell with the specified initial value, returning its ID.
     pub fn create_input(&mut self, initial: T) -> InputCellId {
         self.id += 1;
-        let input_cell_id = InputCellId(self.id);
+        let input_cell_id = InputCellId {
+            reactor: self.reactor_id,
+            id: self.id,
+        };
         let cell = Cell::Input(InputCell(initial));
         self.input_cells.insert(self.id, cell);
 
@@ -158,10 +478,14 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
     // You do not need to reject compute functions that expect more arguments than there are
     // dependencies (how would you check for this, anyway?).
     //
-    // If any dependency doesn't exist, returns an Err with that nonexistent dependency.
+    // If any dependency doesn't exist, returns a `NonexistentDependency` Err with that nonexistent
+    // dependency.
     // (If multiple dependencies do not exist, exactly which one is returned is not defined and
     // will not be tested)
     //
+    // If there are more dependencies than allowed by `set_max_fan_in`, returns a
+    // `TooManyDependencies` Err.
+    //
     // Notice that there is no way to *remove* a cell.
     // This means that you may assume, without checking, that if the dependencies exist at creation
     // time they will continue to exist as long as the Reactor exists.
@@ -169,31 +493,266 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
         &mut self,
         dependencies: &[CellId],
         compute_func: F,
-    ) -> Result<ComputeCellId, CellId> {
+    ) -> Result<ComputeCellId, CreateComputeError> {
+        self.check_fan_in(dependencies.len())?;
         for cell_id in dependencies {
             if self.value(*cell_id).is_none() {
-                return Err(*cell_id);
+                return Err(CreateComputeError::NonexistentDependency(*cell_id));
             }
         }
 
+        Ok(self.insert_compute(dependencies, Box::new(compute_func)))
+    }
+
+    /// Creates a compute cell from exactly `N` dependencies, whose values are passed to
+    /// `compute_func` as an array, so that the arity is checked at compile time.
+    ///
+    /// Errors are reported as for `create_compute`.
+    ///
+    /// ```compile_fail
+    /// let mut r = react::Reactor::new();
+    /// let a = react::CellId::Input(r.create_input(3));
+    /// let b = react::CellId::Input(r.create_input(1));
+    /// r.create_compute_n([a, b], |[x, y, z]: [i32; 3]| x - y - z).unwrap();
+    /// ```
+    pub fn create_compute_n<const N: usize, F: Fn([T; N]) -> T + 'a>(
+        &mut self,
+        dependencies: [CellId; N],
+        compute_func: F,
+    ) -> Result<ComputeCellId, CreateComputeError> {
+        self.create_compute(&dependencies, move |values| {
+            let values: [T; N] = values
+                .try_into()
+                .expect("a compute cell receives one value per dependency");
+            compute_func(values)
+        })
+    }
+
+    // Creates a batch of compute cells, in order, returning their ids.
+    //
+    // Each spec may depend on existing cells or, through `BatchDep::Earlier`, on cells created by
+    // earlier specs of the same batch. If any dependency can't be resolved, or a spec has more
+    // dependencies than allowed by `set_max_fan_in`, returns an Err with the index of the first
+    // offending spec, and no cell of the batch is created.
+    pub fn create_compute_many(
+        &mut self,
+        specs: Vec<(Vec<BatchDep>, ComputeFn<'a, T>)>,
+    ) -> Result<Vec<ComputeCellId>, usize> {
+        for (index, (dependencies, _)) in specs.iter().enumerate() {
+            let resolvable = dependencies.iter().all(|dependency| match dependency {
+                BatchDep::Cell(cell_id) => self.value(*cell_id).is_some(),
+                BatchDep::Earlier(earlier) => *earlier < index,
+            });
+            if !resolvable || self.check_fan_in(dependencies.len()).is_err() {
+                return Err(index);
+            }
+        }
+
+        let mut ids = Vec::with_capacity(specs.len());
+        for (dependencies, compute_func) in specs {
+            let dependencies = dependencies
+                .into_iter()
+                .map(|dependency| match dependency {
+                    BatchDep::Cell(cell_id) => cell_id,
+                    BatchDep::Earlier(earlier) => CellId::Compute(ids[earlier]),
+                })
+                .collect::<Vec<_>>();
+            ids.push(self.insert_compute(&dependencies, compute_func));
+        }
+        Ok(ids)
+    }
+
+    // Rebuilds the map from every cell to the compute cells depending on it, from the
+    // dependencies of each compute cell.
+    pub fn rebuild_dependency_index(&mut self) {
+        self.dependencies.clear();
+        for id in self.compute_ids() {
+            if let Some(Cell::Compute(cell)) = self.compute_cells.get(&id) {
+                for cell_id in &cell.dependencies {
+                    self.dependencies
+                        .entry(*cell_id)
+                        .or_default()
+                        .push(CellId::Compute(id));
+                }
+            }
+        }
+    }
+
+    // Limits how many dependencies a compute cell created from now on may have. There is no limit
+    // by default.
+    pub fn set_max_fan_in(&mut self, limit: usize) {
+        self.max_fan_in = Some(limit);
+    }
+
+    fn check_fan_in(&self, got: usize) -> Result<(), CreateComputeError> {
+        match self.max_fan_in {
+            Some(limit) if got > limit => {
+                Err(CreateComputeError::TooManyDependencies { limit, got })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // Creates a compute cell whose dependencies are known to exist.
+    fn insert_compute(
+        &mut self,
+        dependencies: &[CellId],
+        compute_func: ComputeFn<'a, T>,
+    ) -> ComputeCellId {
         let values = self.get_cells_values(dependencies.to_vec());
+        let depth = 1 + dependencies
+            .iter()
+            .map(|id| self.depth_of(id))
+            .max()
+            .unwrap_or(0);
 
         self.id += 1;
         let compute_cell = ComputeCell {
             value: compute_func(&values),
-            func: Box::new(compute_func),
+            func: compute_func,
             dependencies: dependencies.to_vec(),
+            frozen: false,
+            overridden: false,
+            depth,
         };
         let cell = Cell::Compute(compute_cell);
         self.compute_cells.insert(self.id, cell);
-        let compute_cell_id = ComputeCellId(self.id);
+        let compute_cell_id = ComputeCellId {
+            reactor: self.reactor_id,
+            id: self.id,
+        };
         for cell_id in dependencies {
             self.dependencies
                 .entry(*cell_id)
                 .and_modify(|c| c.push(CellId::Compute(compute_cell_id)))
                 .or_insert(vec![CellId::Compute(compute_cell_id)]);
         }
-        Ok(compute_cell_id)
+        compute_cell_id
+    }
+
+    // Creates a compute cell whose value is the result of folding the values of `dependencies`,
+    // in order, through `f`, starting from `init`.
+    //
+    // Errors are reported as for `create_compute`.
+    pub fn create_fold<F: Fn(T, T) -> T + 'a>(
+        &mut self,
+        dependencies: &[CellId],
+        init: T,
+        f: F,
+    ) -> Result<ComputeCellId, CreateComputeError> {
+        self.create_compute(dependencies, move |values| {
+            values.iter().fold(init, |acc, value| f(acc, *value))
+        })
+    }
+
+    // Creates a compute cell whose compute function receives the values of `dependencies` along
+    // with the fixed `consts`.
+    //
+    // Only `dependencies` are tracked as dependencies; errors are reported as for
+    // `create_compute`.
+    pub fn create_compute_with_const<F: Fn(&[T], &[T]) -> T + 'a>(
+        &mut self,
+        dependencies: &[CellId],
+        consts: &[T],
+        compute_func: F,
+    ) -> Result<ComputeCellId, CreateComputeError> {
+        let consts = consts.to_vec();
+        self.create_compute(dependencies, move |values| compute_func(values, &consts))
+    }
+
+    // Replaces the compute function of the specified compute cell.
+    //
+    // The cell is recomputed with the new function and the change is propagated to its
+    // dependents, running callbacks for every compute cell whose value changed.
+    //
+    // Returns false if the cell does not exist.
+    pub fn update_compute_fn<F: Fn(&[T]) -> T + 'a>(&mut self, id: ComputeCellId, f: F) -> bool {
+        if !self.check_if_compute_cell_exist(id) {
+            return false;
+        }
+        if let Some(Cell::Compute(cell)) = self.compute_cells.get_mut(&id) {
+            cell.func = Box::new(f);
+            if cell.is_pinned() {
+                return true;
+            }
+        }
+        self.refresh_compute_cell(id);
+        true
+    }
+
+    // Freezes the specified compute cell: it keeps its current value, and stops recomputing when
+    // its dependencies change, until it is unfrozen.
+    //
+    // Returns false if the cell does not exist.
+    pub fn freeze(&mut self, id: ComputeCellId) -> bool {
+        self.set_frozen(id, true)
+    }
+
+    // Unfreezes the specified compute cell, immediately recomputing it and propagating the change
+    // (including callbacks) if its value differs from the frozen one.
+    //
+    // Returns false if the cell does not exist.
+    pub fn unfreeze(&mut self, id: ComputeCellId) -> bool {
+        if !self.set_frozen(id, false) {
+            return false;
+        }
+        self.refresh_compute_cell(id);
+        true
+    }
+
+    // Forces the value of the specified compute cell, propagating the change (including
+    // callbacks) if it differs from the current one. The cell keeps the forced value, whatever
+    // its dependencies, until `clear_override` is called.
+    //
+    // Returns false if the cell does not exist.
+    pub fn override_value(&mut self, id: ComputeCellId, value: T) -> bool {
+        if !self.check_if_compute_cell_exist(id) {
+            return false;
+        }
+        if let Some(Cell::Compute(cell)) = self.compute_cells.get_mut(&id) {
+            cell.overridden = true;
+            if (self.value_eq)(&cell.value, &value) {
+                return true;
+            }
+            cell.value = value;
+        }
+        let mut changed = vec![id];
+        changed.extend(self.update_dependencies(&[CellId::Compute(id)]));
+        self.run_callbacks(&changed);
+        true
+    }
+
+    // Lifts the override of the specified compute cell, immediately recomputing it from its
+    // dependencies and propagating the change (including callbacks) if its value differs.
+    //
+    // Returns false if the cell does not exist.
+    pub fn clear_override(&mut self, id: ComputeCellId) -> bool {
+        if !self.check_if_compute_cell_exist(id) {
+            return false;
+        }
+        if let Some(Cell::Compute(cell)) = self.compute_cells.get_mut(&id) {
+            cell.overridden = false;
+        }
+        self.refresh_compute_cell(id);
+        true
+    }
+
+    // Recomputes every compute cell (except frozen and overridden ones) from its dependencies,
+    // without running any callbacks.
+    pub fn recompute_all(&mut self) {
+        // Ascending ids are a topological order, since a cell only depends on earlier cells.
+        for id in self.compute_ids() {
+            let new_value = match self.compute_cells.get(&id) {
+                Some(Cell::Compute(cell)) if !cell.is_pinned() => {
+                    let values = self.get_cells_values(cell.dependencies.clone());
+                    (cell.func)(&values)
+                }
+                _ => continue,
+            };
+            if let Some(Cell::Compute(cell)) = self.compute_cells.get_mut(&id) {
+                cell.value = new_value;
+            }
+        }
     }
 
     // Retrieves the current value of the cell, or None if the cell does not exist.
@@ -203,11 +762,205 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
     //
     // It turns out this introduces a significant amount of extra complexity to this exercise.
     // We chose not to cover this here, since this exercise is probably enough work as-is.
+    //
+    // Ids handed out by a different reactor never refer to a cell of this one.
     pub fn value(&self, id: CellId) -> Option<T> {
+        if !self.check_if_cell_owned(id) {
+            return None;
+        }
         match id {
-            CellId::Input(cell_id) => self.input_cells.get(&cell_id).map(|i| i.get_value(self)),
-            CellId::Compute(cell_id) => self.compute_cells.get(&cell_id).map(|c| c.get_value(self)),
+            CellId::Input(cell_id) => self.input_cells.get(&cell_id).map(|i| i.get_value()),
+            CellId::Compute(cell_id) => self.compute_cells.get(&cell_id).map(|c| c.get_value()),
+        }
+    }
+
+    // Returns the value stored in the specified compute cell, without recomputing it, or None if
+    // the cell does not exist. The value is stale while the cell is frozen or overridden, or when
+    // its compute function reads state the reactor cannot see.
+    pub fn cached_value(&self, id: ComputeCellId) -> Option<T> {
+        if !self.check_if_compute_cell_exist(id) {
+            return None;
+        }
+        match self.compute_cells.get(&id) {
+            Some(Cell::Compute(cell)) => Some(cell.value),
+            _ => None,
+        }
+    }
+
+    // Returns the value of each of the specified cells, in the same order, with None for the
+    // cells that do not exist.
+    pub fn values_of(&self, ids: &[CellId]) -> Vec<Option<T>> {
+        ids.iter().map(|id| self.value(*id)).collect()
+    }
+
+    // Describes the specified cell, telling a missing cell apart from a stale compute cell.
+    pub fn inspect(&self, id: CellId) -> CellStatus<T> {
+        if !self.check_if_cell_owned(id) {
+            return CellStatus::Missing;
+        }
+        let cell = match id {
+            CellId::Input(cell_id) => self.input_cells.get(&cell_id),
+            CellId::Compute(cell_id) => self.compute_cells.get(&cell_id),
+        };
+        match cell {
+            None => CellStatus::Missing,
+            Some(Cell::Input(input_cell)) => CellStatus::Input(input_cell.0),
+            Some(Cell::Compute(compute_cell)) => {
+                let values = self.get_cells_values(compute_cell.dependencies.clone());
+                let fresh = (compute_cell.func)(&values);
+                CellStatus::Compute {
+                    value: compute_cell.value,
+                    dirty: !(self.value_eq)(&compute_cell.value, &fresh),
+                }
+            }
+        }
+    }
+
+    // Returns the ids of all input cells, in the order they were created.
+    pub fn input_ids(&self) -> Vec<InputCellId> {
+        let mut ids = self
+            .input_cells
+            .keys()
+            .map(|&id| InputCellId {
+                reactor: self.reactor_id,
+                id,
+            })
+            .collect::<Vec<_>>();
+        ids.sort_by_key(|id| **id);
+        ids
+    }
+
+    // Returns the ids of all compute cells, in the order they were created.
+    pub fn compute_ids(&self) -> Vec<ComputeCellId> {
+        let mut ids = self
+            .compute_cells
+            .keys()
+            .map(|&id| ComputeCellId {
+                reactor: self.reactor_id,
+                id,
+            })
+            .collect::<Vec<_>>();
+        ids.sort_by_key(|id| **id);
+        ids
+    }
+
+    // Maps every cell to its (in-degree, out-degree): how many cells it depends on and how many
+    // compute cells depend on it.
+    pub fn degrees(&self) -> HashMap<CellId, (usize, usize)> {
+        let out_degree = |cell_id: &CellId| self.dependencies.get(cell_id).map_or(0, Vec::len);
+        let inputs = self.input_ids().into_iter().map(|id| {
+            let cell_id = CellId::Input(id);
+            (cell_id, (0, out_degree(&cell_id)))
+        });
+        let computes = self.compute_ids().into_iter().map(|id| {
+            let cell_id = CellId::Compute(id);
+            let in_degree = match self.compute_cells.get(&id) {
+                Some(Cell::Compute(cell)) => cell.dependencies.len(),
+                _ => 0,
+            };
+            (cell_id, (in_degree, out_degree(&cell_id)))
+        });
+        inputs.chain(computes).collect()
+    }
+
+    // Returns the compute cells whose value equals `target`, in the order they were created.
+    //
+    // Values are kept up to date by propagation, so no recomputation is needed; frozen and
+    // overridden cells are matched on the value they hold.
+    pub fn cells_with_value(&self, target: T) -> Vec<ComputeCellId> {
+        self.compute_ids()
+            .into_iter()
+            .filter(|id| self.cached_value(*id) == Some(target))
+            .collect()
+    }
+
+    // Returns the compute cells that only depend on input cells, in the order they were created.
+    pub fn leaf_compute_cells(&self) -> Vec<ComputeCellId> {
+        self.compute_ids()
+            .into_iter()
+            .filter(|id| match self.compute_cells.get(id) {
+                Some(Cell::Compute(cell)) => cell
+                    .dependencies
+                    .iter()
+                    .all(|dependency| matches!(dependency, CellId::Input(_))),
+                _ => false,
+            })
+            .collect()
+    }
+
+    // Returns whether no compute cell depends on the specified cell, which is also the case for a
+    // cell that does not exist.
+    pub fn safe_to_remove(&self, id: CellId) -> bool {
+        self.dependencies
+            .get(&id)
+            .is_none_or(|dependents| dependents.is_empty())
+    }
+
+    // Returns the shortest chain of cells through which a change of `from` reaches `to`, starting
+    // with `from` and ending with `to`.
+    //
+    // Returns None if `to` does not (transitively) depend on `from`.
+    pub fn path_between(&self, from: CellId, to: ComputeCellId) -> Option<Vec<CellId>> {
+        if !self.check_if_cell_owned(from) || !self.check_if_compute_cell_exist(to) {
+            return None;
+        }
+        let to = CellId::Compute(to);
+        let mut previous = HashMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(cell_id) = queue.pop_front() {
+            for &dependent in self.dependencies.get(&cell_id).into_iter().flatten() {
+                if previous.contains_key(&dependent) {
+                    continue;
+                }
+                previous.insert(dependent, cell_id);
+                if dependent == to {
+                    let mut path = vec![to];
+                    let mut current = to;
+                    while current != from {
+                        current = previous[&current];
+                        path.push(current);
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(dependent);
+            }
+        }
+        None
+    }
+
+    // Captures the values of the specified compute cells and of every cell they (transitively)
+    // depend on. Roots that do not exist are ignored.
+    pub fn snapshot_subgraph(&self, roots: &[ComputeCellId]) -> ReactorState<T> {
+        // Input and compute cells share one id sequence, so the ids order all of the cells.
+        let mut reachable = BTreeMap::new();
+        let mut stack = roots
+            .iter()
+            .filter(|id| self.check_if_compute_cell_exist(**id))
+            .map(|id| CellId::Compute(*id))
+            .collect::<Vec<_>>();
+        while let Some(cell_id) = stack.pop() {
+            if reachable.insert(cell_id.get_id(), cell_id).is_some() {
+                continue;
+            }
+            if let CellId::Compute(id) = cell_id {
+                if let Some(Cell::Compute(cell)) = self.compute_cells.get(&id) {
+                    stack.extend(cell.dependencies.iter().copied());
+                }
+            }
+        }
+
+        let mut inputs = Vec::new();
+        let mut computes = Vec::new();
+        for cell_id in reachable.into_values() {
+            if let Some(value) = self.value(cell_id) {
+                match cell_id {
+                    CellId::Input(id) => inputs.push((id, value)),
+                    CellId::Compute(id) => computes.push((id, value)),
+                }
+            }
         }
+        ReactorState { inputs, computes }
     }
 
     // Sets the value of the specified input cell.
@@ -218,17 +971,232 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
     // a `set_value(&mut self, new_value: T)` method on `Cell`.
     //
     // As before, that turned out to add too much extra complexity.
+    //
+    // Writing the value the cell already holds is a no-op: nothing is recomputed and no callbacks
+    // are run.
     pub fn set_value(&mut self, id: InputCellId, new_value: T) -> bool {
-        if let Some(e) = self.input_cells.get_mut(&id) {
-            let new_cell = Cell::Input(InputCell(new_value));
-            *e = new_cell;
-            let mut changed = HashMap::new();
-            self.update_dependencies(&CellId::Input(id), &mut changed);
-            self.run_callbacks(&changed);
-            true
-        } else {
-            false
+        self.set_value_report(id, new_value).is_some()
+    }
+
+    // Sets the value of the specified input cell, reporting which compute cells changed as a
+    // result.
+    //
+    // Returns None if the cell does not exist, or else the ids of the compute cells whose value
+    // changed, in topological order (every cell comes after the cells it depends on).
+    //
+    // This includes the cells changed by writes that callbacks queued through a `DeferredWriter`
+    // during the call. A cell that changed more than once is listed once.
+    pub fn set_value_report(
+        &mut self,
+        id: InputCellId,
+        new_value: T,
+    ) -> Option<Vec<ComputeCellId>> {
+        if !self.check_if_input_cell_exist(id) {
+            return None;
+        }
+        Some(self.apply_values(&[(id, new_value)]))
+    }
+
+    // Returns a handle through which callbacks can write to this reactor's input cells.
+    pub fn deferred_writer(&self) -> DeferredWriter<T> {
+        DeferredWriter {
+            queue: Rc::clone(&self.deferred_writes),
+        }
+    }
+
+    // Applies the writes queued by `DeferredWriter`s, in order, until no more are queued.
+    //
+    // This happens after every propagation, so it only needs to be called for writes queued
+    // outside of callbacks. Writes queued while the writes are being applied are applied in turn,
+    // after the current ones, rather than nested inside them.
+    pub fn apply_deferred_writes(&mut self) {
+        self.flush_deferred_writes();
+    }
+
+    // Returns the ids of the compute cells whose value changed, possibly more than once each.
+    fn flush_deferred_writes(&mut self) -> Vec<ComputeCellId> {
+        let mut changed = Vec::new();
+        if self.propagating {
+            return changed;
+        }
+        self.propagating = true;
+        loop {
+            let mut writes = self.deferred_writes.take();
+            for (queue, inputs) in &self.merged_writes {
+                writes.extend(
+                    queue
+                        .take()
+                        .into_iter()
+                        .filter_map(|(id, value)| inputs.get(&id).map(|&id| (id, value))),
+                );
+            }
+            // Once every writer of a merged queue is gone, nothing can be queued there any more.
+            self.merged_writes
+                .retain(|(queue, _)| Rc::strong_count(queue) > 1);
+            if writes.is_empty() {
+                break;
+            }
+            let writes = writes
+                .into_iter()
+                .filter(|&(id, _)| self.check_if_input_cell_exist(id))
+                .collect::<Vec<_>>();
+            changed.extend(self.apply_values(&writes));
+        }
+        self.propagating = false;
+        changed
+    }
+
+    // Returns a guard that reads and mutates the value of the specified input cell, or None if the
+    // cell does not exist.
+    //
+    // The new value is propagated once, when the guard is dropped, and only if it was mutated.
+    pub fn input(&mut self, id: InputCellId) -> Option<InputGuard<'_, 'a, T>> {
+        let value = self.value(CellId::Input(id))?;
+        Some(InputGuard {
+            reactor: self,
+            id,
+            value,
+            modified: false,
+        })
+    }
+
+    // Turns recording of `set_value` operations on or off.
+    //
+    // While recording, every successful `set_value` appends the input cell and its new value to
+    // the event log. Turning recording off keeps the entries recorded so far.
+    pub fn set_recording(&mut self, record: bool) {
+        self.record = record;
+    }
+
+    // Returns the recorded `set_value` operations, oldest first.
+    pub fn event_log(&self) -> &[(InputCellId, T)] {
+        &self.event_log
+    }
+
+    // Starts keeping the `capacity` most recent `set_value` operations, evicting the oldest one
+    // once full, and drops any previously kept operations.
+    //
+    // Unlike the event log, this never grows beyond `capacity` entries.
+    pub fn enable_recent_log(&mut self, capacity: usize) {
+        self.recent_capacity = Some(capacity);
+        self.recent_log = VecDeque::with_capacity(capacity);
+    }
+
+    // Returns the most recent `set_value` operations, oldest first.
+    pub fn recent_log(&self) -> &[(InputCellId, T)] {
+        // The write path keeps the ring contiguous, so the first slice holds every entry.
+        self.recent_log.as_slices().0
+    }
+
+    // Applies the given `set_value` operations in order.
+    //
+    // The log is expected to come from a reactor with the same topology, e.g. one built by the
+    // same sequence of `create_input` and `create_compute` calls. Its ids are therefore taken to
+    // refer to the cells of this reactor, even if the log was recorded by another one.
+    pub fn replay(&mut self, log: &[(InputCellId, T)]) {
+        for (id, value) in log {
+            let id = InputCellId {
+                reactor: self.reactor_id,
+                ..*id
+            };
+            self.set_value(id, *value);
+        }
+    }
+
+    // Moves all of the cells of `other`, along with their callbacks, into this reactor under fresh
+    // ids. A compute cell whose id is a key of `closures` gets that compute function in place of
+    // its own.
+    //
+    // `DeferredWriter`s of `other` keep working: their writes are applied to the imported input
+    // cells, after the writes queued through this reactor's own writers.
+    //
+    // Returns the new id of every imported cell, keyed by its id in `other`.
+    pub fn merge(
+        &mut self,
+        other: Reactor<'a, T>,
+        closures: HashMap<ComputeCellId, ComputeFn<'a, T>>,
+    ) -> HashMap<CellId, CellId> {
+        let Reactor {
+            reactor_id,
+            mut input_cells,
+            mut compute_cells,
+            mut callbacks,
+            deferred_writes,
+            merged_writes,
+            ..
+        } = other;
+        let mut closures = closures
+            .into_iter()
+            .filter(|(id, _)| id.reactor == reactor_id)
+            .map(|(id, f)| (id.id, f))
+            .collect::<HashMap<_, _>>();
+
+        // Importing in ascending id order creates every cell after the cells it depends on.
+        let mut ids = input_cells
+            .keys()
+            .chain(compute_cells.keys())
+            .copied()
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+
+        let mut remap = HashMap::new();
+        for id in ids {
+            if let Some(Cell::Input(input_cell)) = input_cells.remove(&id) {
+                let new_id = self.create_input(input_cell.0);
+                remap.insert(
+                    CellId::Input(InputCellId {
+                        reactor: reactor_id,
+                        id,
+                    }),
+                    CellId::Input(new_id),
+                );
+            } else if let Some(Cell::Compute(cell)) = compute_cells.remove(&id) {
+                let old_id = ComputeCellId {
+                    reactor: reactor_id,
+                    id,
+                };
+                let dependencies = cell
+                    .dependencies
+                    .iter()
+                    .map(|dependency| remap[dependency])
+                    .collect::<Vec<_>>();
+                let (value, frozen, overridden) = (cell.value, cell.frozen, cell.overridden);
+                let func = closures.remove(&id).unwrap_or(cell.func);
+                let new_id = self.insert_compute(&dependencies, func);
+                // Pinned cells keep their value rather than being recomputed.
+                if let Some(Cell::Compute(new_cell)) = self.compute_cells.get_mut(&new_id) {
+                    if frozen || overridden {
+                        new_cell.value = value;
+                    }
+                    new_cell.frozen = frozen;
+                    new_cell.overridden = overridden;
+                }
+                if let Some(callback_entry) = callbacks.remove(&old_id) {
+                    self.callbacks.insert(new_id, callback_entry);
+                }
+                remap.insert(CellId::Compute(old_id), CellId::Compute(new_id));
+            }
+        }
+
+        let inputs = remap
+            .iter()
+            .filter_map(|(old_id, new_id)| match (old_id, new_id) {
+                (CellId::Input(old_id), CellId::Input(new_id)) => Some((*old_id, *new_id)),
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        for (queue, queue_inputs) in merged_writes {
+            let queue_inputs = queue_inputs
+                .into_iter()
+                .filter_map(|(writer_id, id)| inputs.get(&id).map(|&new_id| (writer_id, new_id)))
+                .collect();
+            self.merged_writes.push((queue, queue_inputs));
         }
+        // Only keep the queue if some writer (or pending write) can still use it.
+        if Rc::strong_count(&deferred_writes) > 1 || !deferred_writes.borrow().is_empty() {
+            self.merged_writes.push((deferred_writes, inputs));
+        }
+        remap
     }
 
     // Adds a callback to the specified compute cell.
@@ -243,16 +1211,85 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
     // * Exactly once if the compute cell's value changed as a result of the set_value call.
     //   The value passed to the callback should be the final value of the compute cell after the
     //   set_value call.
+    //
+    // The one exception is writes queued through a `DeferredWriter` during the set_value call:
+    // they are applied as further propagations, so a callback runs again each time they change
+    // its cell.
     pub fn add_callback<F: FnMut(T) + 'a>(
         &mut self,
         id: ComputeCellId,
         callback: F,
+    ) -> Option<CallbackId> {
+        let mut callback = callback;
+        self.insert_callback(
+            id,
+            Box::new(move |value| {
+                callback(value);
+                true
+            }),
+        )
+    }
+
+    // Adds a callback to the specified compute cell that is removed once `until` holds for the
+    // cell's value. The callback is still called with the value that satisfies `until`.
+    //
+    // Returns the ID of the just-added callback, or None if the cell doesn't exist.
+    pub fn add_callback_until<P: Fn(T) -> bool + 'a, F: FnMut(T) + 'a>(
+        &mut self,
+        id: ComputeCellId,
+        until: P,
+        callback: F,
+    ) -> Option<CallbackId> {
+        let mut callback = callback;
+        self.insert_callback(
+            id,
+            Box::new(move |value| {
+                callback(value);
+                !until(value)
+            }),
+        )
+    }
+
+    // Adds a callback to the specified compute cell that is only held weakly.
+    //
+    // Returns the ID of the just-added callback, or None if the cell doesn't exist.
+    //
+    // Once the callback has been dropped by its owner it is no longer called or reported by
+    // `callback_count`, `callback_ids` and `observed_cells`, and it is removed the next time the
+    // cell's value changes.
+    pub fn add_weak_callback(
+        &mut self,
+        id: ComputeCellId,
+        weak: Weak<RefCell<dyn 'a + FnMut(T)>>,
+    ) -> Option<CallbackId> {
+        let probe = Weak::clone(&weak);
+        let callback_id = self.insert_callback(
+            id,
+            Box::new(move |value| match weak.upgrade() {
+                Some(callback) => {
+                    (callback.borrow_mut())(value);
+                    true
+                }
+                None => false,
+            }),
+        )?;
+        if let Some(callback_entry) = self.callbacks.get_mut(&id) {
+            callback_entry
+                .weak_probes
+                .insert(callback_id, Box::new(move || probe.strong_count() > 0));
+        }
+        Some(callback_id)
+    }
+
+    fn insert_callback(
+        &mut self,
+        id: ComputeCellId,
+        callback_box: CallbackFn<'a, T>,
     ) -> Option<CallbackId> {
         if !self.check_if_compute_cell_exist(id) {
             return None;
         }
 
-        let callback_box = Box::new(callback);
         let callback_id = match self.callbacks.get_mut(&id) {
             Some(callback_entry) => {
                 callback_entry.id += 1;
@@ -264,7 +1301,9 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
             None => {
                 let mut callback_entry = CallbackEntry {
                     id: 0,
-                    callbacks: HashMap::new(),
+                    callbacks: BTreeMap::new(),
+                    tags: HashMap::new(),
+                    weak_probes: HashMap::new(),
                 };
                 callback_entry.id += 1;
                 let callback_id = CallbackId(callback_entry.id);
@@ -295,17 +1334,194 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
             return Err(RemoveCallbackError::NonexistentCallback);
         }
         let callback_entry = callbacks.unwrap();
-        if callback_entry.callbacks.get(&callback).is_none() {
+        if !callback_entry.callbacks.contains_key(&callback) {
             return Err(RemoveCallbackError::NonexistentCallback);
         }
 
         callback_entry.callbacks.remove(&callback);
+        callback_entry.tags.remove(&callback);
+        callback_entry.weak_probes.remove(&callback);
 
         Ok(())
     }
 
+    // Adds a callback to the specified compute cell under the given tag, so that it can later be
+    // removed together with the other callbacks sharing that tag.
+    //
+    // Returns the ID of the just-added callback, or None if the cell doesn't exist.
+    pub fn add_callback_tagged<F: FnMut(T) + 'a>(
+        &mut self,
+        id: ComputeCellId,
+        tag: u64,
+        callback: F,
+    ) -> Option<CallbackId> {
+        let callback_id = self.add_callback(id, callback)?;
+        if let Some(callback_entry) = self.callbacks.get_mut(&id) {
+            callback_entry.tags.insert(callback_id, tag);
+        }
+        Some(callback_id)
+    }
+
+    // Removes every callback on the specified compute cell that was added under the given tag.
+    //
+    // Returns how many callbacks were removed, or 0 if the cell does not exist.
+    pub fn remove_callbacks_by_tag(&mut self, cell: ComputeCellId, tag: u64) -> usize {
+        if !self.check_if_compute_cell_exist(cell) {
+            return 0;
+        }
+        let Some(callback_entry) = self.callbacks.get_mut(&cell) else {
+            return 0;
+        };
+        let tagged = callback_entry
+            .tags
+            .iter()
+            .filter(|(_, &t)| t == tag)
+            .map(|(&callback_id, _)| callback_id)
+            .collect::<Vec<_>>();
+        let mut removed = 0;
+        for callback_id in tagged {
+            callback_entry.tags.remove(&callback_id);
+            callback_entry.weak_probes.remove(&callback_id);
+            if callback_entry.callbacks.remove(&callback_id).is_some() {
+                removed += 1;
+            }
+        }
+        removed
+    }
+
+    // Returns the compute cells that have at least one callback, in the order they were created.
+    pub fn observed_cells(&self) -> Vec<ComputeCellId> {
+        let mut ids = self
+            .callbacks
+            .iter()
+            .filter(|(_, callback_entry)| callback_entry.live_ids().next().is_some())
+            .map(|(&id, _)| id)
+            .collect::<Vec<_>>();
+        ids.sort_by_key(|id| **id);
+        ids
+    }
+
+    // Removes every callback from every compute cell, returning how many were removed. Weak
+    // callbacks whose owner dropped them are not counted, as for `callback_count`.
+    //
+    // Callback ids keep increasing, so ids handed out before the call are never reused.
+    pub fn clear_all_callbacks(&mut self) -> usize {
+        let mut removed = 0;
+        for callback_entry in self.callbacks.values_mut() {
+            removed += callback_entry.live_ids().count();
+            callback_entry.callbacks.clear();
+            callback_entry.tags.clear();
+            callback_entry.weak_probes.clear();
+        }
+        removed
+    }
+
+    // Returns the number of callbacks registered on the specified compute cell, or 0 if the cell
+    // does not exist.
+    pub fn callback_count(&self, cell: ComputeCellId) -> usize {
+        if !self.check_if_compute_cell_exist(cell) {
+            return 0;
+        }
+        self.callbacks
+            .get(&cell)
+            .map_or(0, |callback_entry| callback_entry.live_ids().count())
+    }
+
+    // Returns the ids of the callbacks registered on the specified compute cell, in the order they
+    // were added, or an empty Vec if the cell does not exist.
+    pub fn callback_ids(&self, cell: ComputeCellId) -> Vec<CallbackId> {
+        if !self.check_if_compute_cell_exist(cell) {
+            return Vec::new();
+        }
+        self.callbacks
+            .get(&cell)
+            .map(|callback_entry| callback_entry.live_ids().collect())
+            .unwrap_or_default()
+    }
+
+    // Returns a rough estimate of the memory used by the cells, dependency edges and callbacks.
+    //
+    // This is an approximation: hash map overhead and the environments captured by compute
+    // functions and callbacks are not counted.
+    pub fn estimated_size_bytes(&self) -> usize {
+        let cell_count = self.input_cells.len() + self.compute_cells.len();
+        let cells = cell_count * (size_of::<usize>() + size_of::<Cell<'a, T>>());
+        let dependency_edges = self
+            .compute_cells
+            .values()
+            .map(|cell| match cell {
+                Cell::Compute(compute_cell) => compute_cell.dependencies.len(),
+                Cell::Input(_) => 0,
+            })
+            .sum::<usize>();
+        let dependent_edges = self.dependencies.values().map(Vec::len).sum::<usize>();
+        let edges = (dependency_edges + dependent_edges) * size_of::<CellId>();
+        let callbacks = self
+            .callbacks
+            .values()
+            .map(|callback_entry| callback_entry.callbacks.len())
+            .sum::<usize>()
+            * (size_of::<CallbackId>() + size_of::<CallbackFn<'a, T>>());
+        size_of::<Self>() + cells + edges + callbacks
+    }
+
+    // Starts counting how many times each compute cell is recomputed by propagation, resetting
+    // any previous counts.
+    pub fn enable_profiling(&mut self) {
+        self.recompute_counts = Some(HashMap::new());
+    }
+
+    // Returns how many times the specified compute cell was recomputed since profiling was
+    // enabled, or None if profiling is disabled or the cell does not exist.
+    pub fn recompute_count(&self, id: ComputeCellId) -> Option<u64> {
+        if !self.check_if_compute_cell_exist(id) {
+            return None;
+        }
+        let counts = self.recompute_counts.as_ref()?;
+        Some(counts.get(&id).copied().unwrap_or(0))
+    }
+
     fn check_if_compute_cell_exist(&self, cell: ComputeCellId) -> bool {
-        self.compute_cells.contains_key(&cell)
+        self.check_if_cell_owned(CellId::Compute(cell)) && self.compute_cells.contains_key(&cell)
+    }
+
+    fn check_if_input_cell_exist(&self, cell: InputCellId) -> bool {
+        self.check_if_cell_owned(CellId::Input(cell)) && self.input_cells.contains_key(&cell)
+    }
+
+    fn check_if_cell_owned(&self, cell: CellId) -> bool {
+        cell.get_reactor_id() == self.reactor_id
+    }
+
+    fn set_frozen(&mut self, id: ComputeCellId, frozen: bool) -> bool {
+        if !self.check_if_compute_cell_exist(id) {
+            return false;
+        }
+        if let Some(Cell::Compute(cell)) = self.compute_cells.get_mut(&id) {
+            cell.frozen = frozen;
+        }
+        true
+    }
+
+    // Recomputes the specified compute cell, propagating the change and running callbacks if its
+    // value changed.
+    fn refresh_compute_cell(&mut self, id: ComputeCellId) {
+        let new_value = match self.compute_cells.get(&id) {
+            Some(Cell::Compute(cell)) if !cell.is_pinned() => {
+                (cell.func)(&self.get_cells_values(cell.dependencies.clone()))
+            }
+            _ => return,
+        };
+
+        if let Some(Cell::Compute(cell)) = self.compute_cells.get_mut(&id) {
+            if (self.value_eq)(&cell.value, &new_value) {
+                return;
+            }
+            cell.value = new_value;
+        }
+        let mut changed = vec![id];
+        changed.extend(self.update_dependencies(&[CellId::Compute(id)]));
+        self.run_callbacks(&changed);
     }
 
     fn get_cells_values(&self, dependencies: Vec<CellId>) -> Vec<T> {
@@ -315,41 +1531,340 @@ impl<'a, T: Copy + PartialEq> Reactor<'a, T> {
             .collect::<Vec<_>>()
     }
 
-    fn update_dependencies(&mut self, cell_id: &CellId, changed: &mut HashMap<ComputeCellId, T>) {
-        if let Some(compute_cell_ids) = self.dependencies.get(cell_id) {
-            for compute_cell_id in compute_cell_ids.clone() {
-                let id = compute_cell_id.get_id();
-                if let Some(Cell::Compute(cell)) = self.compute_cells.get(&id) {
+    // Writes the given values to their (existing) input cells and propagates all of the changes
+    // at once, so that each compute cell is recomputed, and its callbacks run, at most once.
+    //
+    // Returns the ids of the compute cells whose value changed, in topological order.
+    fn apply_values(&mut self, writes: &[(InputCellId, T)]) -> Vec<ComputeCellId> {
+        let mut sources = Vec::new();
+        for &(id, new_value) in writes {
+            if let Some(e) = self.input_cells.get_mut(&id) {
+                if self.record {
+                    self.event_log.push((id, new_value));
+                }
+                if let Some(capacity) = self.recent_capacity.filter(|&capacity| capacity > 0) {
+                    if self.recent_log.len() == capacity {
+                        self.recent_log.pop_front();
+                    }
+                    self.recent_log.push_back((id, new_value));
+                    self.recent_log.make_contiguous();
+                }
+                if let Cell::Input(InputCell(value)) = e {
+                    if *value == new_value {
+                        continue;
+                    }
+                }
+                let new_cell = Cell::Input(InputCell(new_value));
+                *e = new_cell;
+                sources.push(CellId::Input(id));
+            }
+        }
+        let mut changed = self.update_dependencies(&sources);
+        let flushed = self.run_callbacks(&changed);
+        if !flushed.is_empty() {
+            changed.extend(flushed);
+            changed.sort_by_key(|id| (self.depth_of(&CellId::Compute(*id)), **id));
+            changed.dedup();
+        }
+        changed
+    }
+
+    // Recomputes every compute cell affected by a change of `cell_ids`, returning the ids of those
+    // whose value changed, in topological order.
+    //
+    // The affected cells are visited in increasing depth, so each of them is recomputed once,
+    // after all of its dependencies are final.
+    fn update_dependencies(&mut self, cell_ids: &[CellId]) -> Vec<ComputeCellId> {
+        let mut changed = Vec::new();
+        let mut pending = BTreeSet::new();
+        for cell_id in cell_ids {
+            self.schedule_dependents(cell_id, &mut pending);
+        }
+        while let Some((_, id)) = pending.pop_first() {
+            let new_value = match self.compute_cells.get(&id) {
+                Some(Cell::Compute(cell)) if !cell.is_pinned() => {
                     let values = self.get_cells_values(cell.dependencies.clone());
                     let new_value = (cell.func)(&values);
-                    if new_value == cell.value {
+                    if let Some(counts) = &mut self.recompute_counts {
+                        *counts.entry(id).or_insert(0) += 1;
+                    }
+                    // Keeping the old value, rather than storing the new one, means small changes
+                    // are measured against it and so cannot accumulate unnoticed.
+                    if (self.value_eq)(&cell.value, &new_value) {
                         continue;
                     }
-                    self.compute_cells.entry(id).and_modify(|c| {
-                        if let Cell::Compute(compute_cell) = c {
-                            changed.insert(ComputeCellId(id), compute_cell.value);
-                            compute_cell.value = new_value;
-                        }
-                    });
-                    self.update_dependencies(&compute_cell_id, changed);
+                    new_value
                 }
+                _ => continue,
+            };
+            if let Some(Cell::Compute(cell)) = self.compute_cells.get_mut(&id) {
+                cell.value = new_value;
             }
+            let compute_cell_id = ComputeCellId {
+                reactor: self.reactor_id,
+                id,
+            };
+            changed.push(compute_cell_id);
+            self.schedule_dependents(&CellId::Compute(compute_cell_id), &mut pending);
+        }
+        changed
+    }
+
+    // Pending cells are keyed by (depth, id), so every cell is recomputed only after all of the
+    // cells it depends on, however long the paths leading to them.
+    fn schedule_dependents(&self, cell_id: &CellId, pending: &mut BTreeSet<(usize, usize)>) {
+        if let Some(compute_cell_ids) = self.dependencies.get(cell_id) {
+            pending.extend(
+                compute_cell_ids
+                    .iter()
+                    .map(|id| (self.depth_of(id), id.get_id())),
+            );
+        }
+    }
+
+    // Input cells have depth 0.
+    fn depth_of(&self, cell_id: &CellId) -> usize {
+        match cell_id {
+            CellId::Input(_) => 0,
+            CellId::Compute(id) => match self.compute_cells.get(id) {
+                Some(Cell::Compute(cell)) => cell.depth,
+                _ => 0,
+            },
         }
     }
 
-    fn run_callbacks(&mut self, changed: &HashMap<ComputeCellId, T>) {
-        for (computed_cell_id, prev_value) in changed {
+    // Returns the ids of the compute cells changed by the deferred writes applied afterwards.
+    fn run_callbacks(&mut self, changed: &[ComputeCellId]) -> Vec<ComputeCellId> {
+        for computed_cell_id in changed {
             if let Some(value) = self.value(CellId::Compute(*computed_cell_id)) {
-                if value == *prev_value {
-                    continue;
+                if let Some(callback_entry) = self.callbacks.get_mut(computed_cell_id) {
+                    callback_entry.callbacks.retain(|_, func| func(value));
+                    let callbacks = &callback_entry.callbacks;
+                    callback_entry
+                        .tags
+                        .retain(|id, _| callbacks.contains_key(id));
+                    callback_entry
+                        .weak_probes
+                        .retain(|id, _| callbacks.contains_key(id));
                 }
+            }
+        }
+        self.flush_deferred_writes()
+    }
+}
 
-                if let Some(callback_entry) = self.callbacks.get_mut(computed_cell_id) {
-                    for func in callback_entry.callbacks.values_mut() {
-                        func(value);
-                    }
+impl<'a, T> Reactor<'a, T>
+where
+    T: Copy + PartialEq + Add<Output = T> + Div<Output = T> + From<u16> + 'a,
+{
+    // Creates a compute cell whose value is the arithmetic mean of the values of `dependencies`.
+    // The sum is divided with `T`'s own division, so for integer types the mean is truncated,
+    // e.g. the mean of 1 and 2 is 1.
+    //
+    // There is no mean of nothing, so an empty `dependencies` is rejected with an
+    // `EmptyDependencies` Err. The count must convert into `T`, so more than `u16::MAX`
+    // dependencies are rejected with a `TooManyToAverage` Err. Other errors are reported as for
+    // `create_compute`.
+    pub fn create_average(
+        &mut self,
+        dependencies: &[CellId],
+    ) -> Result<ComputeCellId, CreateComputeError> {
+        if dependencies.is_empty() {
+            return Err(CreateComputeError::EmptyDependencies);
+        }
+        let count = u16::try_from(dependencies.len()).map_err(|_| {
+            CreateComputeError::TooManyToAverage {
+                got: dependencies.len(),
+            }
+        })?;
+        self.create_compute(dependencies, move |values| {
+            let sum = values[1..]
+                .iter()
+                .fold(values[0], |sum, value| sum + *value);
+            sum / T::from(count)
+        })
+    }
+}
+
+impl<'a, T: Copy + PartialEq + fmt::Debug + 'a> Reactor<'a, T> {
+    // Describes every cell, one per line and in the order they were created, with its value and
+    // the cells it depends on, e.g. `compute 3: 4 <- input 1, compute 2`.
+    //
+    // Ids are relative to the reactor, so reactors built by the same operations describe
+    // themselves identically.
+    pub fn to_state_string(&self) -> String {
+        fn describe(cell_id: &CellId) -> String {
+            match cell_id {
+                CellId::Input(id) => format!("input {}", **id),
+                CellId::Compute(id) => format!("compute {}", **id),
+            }
+        }
+
+        let mut cells = self
+            .input_ids()
+            .into_iter()
+            .map(CellId::Input)
+            .chain(self.compute_ids().into_iter().map(CellId::Compute))
+            .collect::<Vec<_>>();
+        cells.sort_by_key(|cell_id| cell_id.get_id());
+
+        let mut state = String::new();
+        for cell_id in cells {
+            let Some(value) = self.value(cell_id) else {
+                continue;
+            };
+            let _ = write!(state, "{}: {:?}", describe(&cell_id), value);
+            if let CellId::Compute(id) = cell_id {
+                if let Some(Cell::Compute(cell)) = self.compute_cells.get(&id) {
+                    let dependencies = cell.dependencies.iter().map(describe).collect::<Vec<_>>();
+                    let _ = write!(state, " <- {}", dependencies.join(", "));
                 }
             }
+            state.push('\n');
+        }
+        state
+    }
+}
+
+impl<'a, T: Copy + PartialEq + Default + 'a> Reactor<'a, T> {
+    // Returns the dependencies of the specified compute cell that currently affect its value, in
+    // the order they were given, or an empty Vec if the cell does not exist.
+    //
+    // This is a heuristic meant for numeric types: each dependency is in turn replaced by
+    // `T::default()` and by the values of the other dependencies, and reported if any of these
+    // changes the result of the compute function.
+    pub fn active_dependencies(&self, id: ComputeCellId) -> Vec<CellId> {
+        if !self.check_if_compute_cell_exist(id) {
+            return Vec::new();
         }
+        let Some(Cell::Compute(cell)) = self.compute_cells.get(&id) else {
+            return Vec::new();
+        };
+        let values = self.get_cells_values(cell.dependencies.clone());
+        let current = (cell.func)(&values);
+        let sentinels = std::iter::once(T::default())
+            .chain(values.iter().copied())
+            .collect::<Vec<_>>();
+
+        let mut active = Vec::new();
+        for (index, dependency) in cell.dependencies.iter().enumerate() {
+            let mut perturbed = values.clone();
+            let sensitive = sentinels
+                .iter()
+                .filter(|sentinel| **sentinel != values[index])
+                .any(|sentinel| {
+                    perturbed[index] = *sentinel;
+                    (cell.func)(&perturbed) != current
+                });
+            if sensitive {
+                active.push(*dependency);
+            }
+        }
+        active
+    }
+}
+
+/// `DebouncedReactor` buffers writes to input cells and propagates them together.
+///
+/// Only the last value written to each input cell is kept; callbacks run once per flush. Pending
+/// writes are flushed by `flush`, once `max_pending` writes have been queued (if set), and when
+/// the adapter is dropped.
+pub struct DebouncedReactor<'r, 'a, T: Copy + PartialEq + 'a> {
+    reactor: &'r mut Reactor<'a, T>,
+    pending: Vec<(InputCellId, T)>,
+    queued: usize,
+    max_pending: Option<usize>,
+}
+
+impl<'r, 'a, T: Copy + PartialEq + 'a> DebouncedReactor<'r, 'a, T> {
+    pub fn new(reactor: &'r mut Reactor<'a, T>) -> Self {
+        DebouncedReactor {
+            reactor,
+            pending: Vec::new(),
+            queued: 0,
+            max_pending: None,
+        }
+    }
+
+    // Creates an adapter that flushes automatically after every `max_pending` queued writes.
+    pub fn with_max_pending(reactor: &'r mut Reactor<'a, T>, max_pending: usize) -> Self {
+        DebouncedReactor {
+            reactor,
+            pending: Vec::new(),
+            queued: 0,
+            max_pending: Some(max_pending),
+        }
+    }
+
+    // Queues a write to the specified input cell.
+    //
+    // Returns false if the cell does not exist.
+    pub fn set_value(&mut self, id: InputCellId, new_value: T) -> bool {
+        if !self.reactor.check_if_input_cell_exist(id) {
+            return false;
+        }
+        match self
+            .pending
+            .iter_mut()
+            .find(|(pending_id, _)| *pending_id == id)
+        {
+            Some((_, value)) => *value = new_value,
+            None => self.pending.push((id, new_value)),
+        }
+        self.queued += 1;
+        if self
+            .max_pending
+            .is_some_and(|max_pending| self.queued >= max_pending)
+        {
+            self.flush();
+        }
+        true
+    }
+
+    // Applies every pending write, returning the ids of the compute cells whose value changed, in
+    // topological order.
+    pub fn flush(&mut self) -> Vec<ComputeCellId> {
+        self.queued = 0;
+        let writes = std::mem::take(&mut self.pending);
+        self.reactor.apply_values(&writes)
+    }
+
+    // Returns the number of input cells with a pending write.
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+impl<'r, 'a, T: Copy + PartialEq + 'a> Drop for DebouncedReactor<'r, 'a, T> {
+    fn drop(&mut self) {
+        if !self.pending.is_empty() {
+            self.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuilding_the_dependency_index_restores_propagation() {
+        let mut reactor = Reactor::new();
+        let input = reactor.create_input(1);
+        let plus_one = reactor
+            .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+            .unwrap();
+        let doubled = reactor
+            .create_compute(&[CellId::Compute(plus_one)], |v| v[0] * 2)
+            .unwrap();
+
+        reactor.dependencies.clear();
+        assert!(reactor.set_value(input, 2));
+        assert_eq!(reactor.value(CellId::Compute(doubled)), Some(4));
+
+        reactor.rebuild_dependency_index();
+        assert!(reactor.set_value(input, 3));
+        assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(4));
+        assert_eq!(reactor.value(CellId::Compute(doubled)), Some(8));
     }
 }
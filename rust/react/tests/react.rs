@@ -51,7 +51,9 @@ fn error_creating_compute_cell_if_input_doesnt_exist() {
     let input = dummy_reactor.create_input(1);
     assert_eq!(
         Reactor::new().create_compute(&[CellId::Input(input)], |_| 0),
-        Err(CellId::Input(input))
+        Err(CreateComputeError::NonexistentDependency(CellId::Input(
+            input
+        )))
     );
 }
 
@@ -64,7 +66,9 @@ fn do_not_break_cell_if_creating_compute_cell_with_valid_and_invalid_input() {
     let input = reactor.create_input(1);
     assert_eq!(
         reactor.create_compute(&[CellId::Input(input), CellId::Input(dummy_cell)], |_| 0),
-        Err(CellId::Input(dummy_cell))
+        Err(CreateComputeError::NonexistentDependency(CellId::Input(
+            dummy_cell
+        )))
     );
     assert!(reactor.set_value(input, 5));
     assert_eq!(reactor.value(CellId::Input(input)), Some(5));
@@ -439,3 +443,1433 @@ fn test_adder_with_boolean_values() {
         );
     }
 }
+
+#[test]
+fn setting_an_input_to_its_current_value_does_not_recompute() {
+    let calls = std::cell::Cell::new(0);
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let _output = reactor
+        .create_compute(&[CellId::Input(input)], |v| {
+            calls.set(calls.get() + 1);
+            v[0] + 1
+        })
+        .unwrap();
+    let before = calls.get();
+    assert!(reactor.set_value(input, 1));
+    assert_eq!(calls.get(), before);
+    assert!(reactor.set_value(input, 2));
+    assert!(calls.get() > before);
+}
+
+#[test]
+fn input_guard_propagates_once_on_drop() {
+    let calls = std::cell::Cell::new(0);
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor
+        .add_callback(output, |v| {
+            calls.set(calls.get() + 1);
+            cb.callback_called(v)
+        })
+        .is_some());
+
+    {
+        let mut guard = reactor.input(input).unwrap();
+        assert_eq!(*guard, 1);
+        *guard += 1;
+        *guard *= 10;
+    }
+    assert_eq!(calls.get(), 1);
+    cb.expect_to_have_been_called_with(21);
+    assert_eq!(reactor.value(CellId::Input(input)), Some(20));
+}
+
+#[test]
+fn input_guard_for_nonexistent_cell() {
+    let mut dummy_reactor = Reactor::new();
+    let input = dummy_reactor.create_input(1);
+    assert!(Reactor::<i32>::new().input(input).is_none());
+}
+
+#[test]
+fn compute_functions_can_be_replaced() {
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(2);
+    let b = reactor.create_input(3);
+    let formula = reactor
+        .create_compute(&[CellId::Input(a), CellId::Input(b)], |v| v[0] + v[1])
+        .unwrap();
+    let plus_one = reactor
+        .create_compute(&[CellId::Compute(formula)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor
+        .add_callback(plus_one, |v| cb.callback_called(v))
+        .is_some());
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(6));
+
+    assert!(reactor.update_compute_fn(formula, |v| v[0] * v[1]));
+    assert_eq!(reactor.value(CellId::Compute(formula)), Some(6));
+    cb.expect_to_have_been_called_with(7);
+
+    assert!(reactor.set_value(a, 4));
+    assert_eq!(reactor.value(CellId::Compute(formula)), Some(12));
+    cb.expect_to_have_been_called_with(13);
+}
+
+#[test]
+fn error_replacing_compute_function_of_nonexistent_cell() {
+    let mut dummy_reactor = Reactor::new();
+    let input = dummy_reactor.create_input(1);
+    let output = dummy_reactor
+        .create_compute(&[CellId::Input(input)], |_| 0)
+        .unwrap();
+    assert!(!Reactor::new().update_compute_fn(output, |_| 1));
+}
+
+#[test]
+fn recorded_set_values_can_be_replayed() {
+    fn build(reactor: &mut Reactor<i32>) -> (InputCellId, InputCellId, ComputeCellId) {
+        let a = reactor.create_input(1);
+        let b = reactor.create_input(2);
+        let sum = reactor
+            .create_compute(&[CellId::Input(a), CellId::Input(b)], |v| v[0] + v[1])
+            .unwrap();
+        (a, b, sum)
+    }
+
+    let mut reactor = Reactor::new();
+    let (a, b, sum) = build(&mut reactor);
+    reactor.set_recording(true);
+    assert!(reactor.set_value(a, 5));
+    assert!(reactor.set_value(b, 7));
+    assert!(reactor.set_value(a, 6));
+    reactor.set_recording(false);
+    assert_eq!(reactor.event_log(), &[(a, 5), (b, 7), (a, 6)]);
+
+    let mut replayed = Reactor::new();
+    let (_, _, replayed_sum) = build(&mut replayed);
+    replayed.replay(reactor.event_log());
+    assert_eq!(
+        replayed.value(CellId::Compute(replayed_sum)),
+        reactor.value(CellId::Compute(sum))
+    );
+    assert_eq!(replayed.value(CellId::Compute(replayed_sum)), Some(13));
+}
+
+#[test]
+fn ids_from_another_reactor_are_rejected() {
+    let mut first = Reactor::new();
+    let first_input = first.create_input(1);
+    let first_output = first
+        .create_compute(&[CellId::Input(first_input)], |v| v[0] + 1)
+        .unwrap();
+
+    let mut second = Reactor::new();
+    let second_input = second.create_input(10);
+    let second_output = second
+        .create_compute(&[CellId::Input(second_input)], |v| v[0] * 2)
+        .unwrap();
+
+    assert!(!second.set_value(first_input, 5));
+    assert_eq!(second.value(CellId::Input(first_input)), None);
+    assert_eq!(second.value(CellId::Input(second_input)), Some(10));
+    assert_eq!(second.value(CellId::Compute(second_output)), Some(20));
+    assert_eq!(
+        second.create_compute(&[CellId::Compute(first_output)], |v| v[0]),
+        Err(CreateComputeError::NonexistentDependency(CellId::Compute(
+            first_output
+        )))
+    );
+    assert_eq!(second.add_callback(first_output, |_| ()), None);
+    assert_eq!(first.value(CellId::Input(first_input)), Some(1));
+}
+
+#[test]
+fn callbacks_can_be_counted() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert_eq!(reactor.callback_count(output), 0);
+
+    let callback = reactor.add_callback(output, |_| ()).unwrap();
+    assert!(reactor.add_callback(output, |_| ()).is_some());
+    assert_eq!(reactor.callback_count(output), 2);
+
+    assert!(reactor.remove_callback(output, callback).is_ok());
+    assert_eq!(reactor.callback_count(output), 1);
+    assert_eq!(Reactor::<i32>::new().callback_count(output), 0);
+}
+
+#[test]
+fn callback_ids_are_listed_in_registration_order() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor.callback_ids(output).is_empty());
+
+    let first = reactor.add_callback(output, |_| ()).unwrap();
+    let second = reactor.add_callback(output, |_| ()).unwrap();
+    let third = reactor.add_callback(output, |_| ()).unwrap();
+    assert_eq!(reactor.callback_ids(output), vec![first, second, third]);
+
+    assert!(reactor.remove_callback(output, second).is_ok());
+    assert_eq!(reactor.callback_ids(output), vec![first, third]);
+}
+
+#[test]
+fn fold_cells_reduce_their_dependencies() {
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(3);
+    let b = reactor.create_input(7);
+    let c = reactor.create_input(5);
+    let max = reactor
+        .create_fold(
+            &[CellId::Input(a), CellId::Input(b), CellId::Input(c)],
+            i32::MIN,
+            |acc, v| acc.max(v),
+        )
+        .unwrap();
+    assert_eq!(reactor.value(CellId::Compute(max)), Some(7));
+
+    assert!(reactor.set_value(c, 12));
+    assert_eq!(reactor.value(CellId::Compute(max)), Some(12));
+    assert!(reactor.set_value(c, -1));
+    assert_eq!(reactor.value(CellId::Compute(max)), Some(7));
+}
+
+#[test]
+fn frozen_compute_cells_keep_their_value() {
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let frozen = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 10)
+        .unwrap();
+    let plus_one = reactor
+        .create_compute(&[CellId::Compute(frozen)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor
+        .add_callback(plus_one, |v| cb.callback_called(v))
+        .is_some());
+
+    assert!(reactor.freeze(frozen));
+    assert!(reactor.set_value(input, 2));
+    assert_eq!(reactor.value(CellId::Compute(frozen)), Some(10));
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(11));
+    cb.expect_not_to_have_been_called();
+
+    assert!(reactor.unfreeze(frozen));
+    assert_eq!(reactor.value(CellId::Compute(frozen)), Some(20));
+    cb.expect_to_have_been_called_with(21);
+
+    assert!(reactor.set_value(input, 3));
+    cb.expect_to_have_been_called_with(31);
+}
+
+#[test]
+fn error_freezing_nonexistent_cell() {
+    let mut dummy_reactor = Reactor::new();
+    let input = dummy_reactor.create_input(1);
+    let output = dummy_reactor
+        .create_compute(&[CellId::Input(input)], |_| 0)
+        .unwrap();
+    let mut reactor = Reactor::<i32>::new();
+    assert!(!reactor.freeze(output));
+    assert!(!reactor.unfreeze(output));
+}
+
+#[test]
+fn cell_ids_are_listed_in_creation_order() {
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(a)], |v| v[0] + 1)
+        .unwrap();
+    let b = reactor.create_input(2);
+    let sum = reactor
+        .create_compute(&[CellId::Compute(plus_one), CellId::Input(b)], |v| {
+            v[0] + v[1]
+        })
+        .unwrap();
+    let c = reactor.create_input(3);
+    assert_eq!(reactor.input_ids(), vec![a, b, c]);
+    assert_eq!(reactor.compute_ids(), vec![plus_one, sum]);
+    assert!(Reactor::<i32>::new().input_ids().is_empty());
+}
+
+#[test]
+fn compute_cells_can_use_constants() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(2);
+    let scaled = reactor
+        .create_compute_with_const(&[CellId::Input(input)], &[10], |v, c| v[0] * c[0])
+        .unwrap();
+    assert_eq!(reactor.value(CellId::Compute(scaled)), Some(20));
+    assert!(reactor.set_value(input, 7));
+    assert_eq!(reactor.value(CellId::Compute(scaled)), Some(70));
+}
+
+#[test]
+fn set_value_reports_changed_cells_in_topological_order() {
+    let cb1 = CallbackRecorder::new();
+    let cb2 = CallbackRecorder::new();
+    let cb3 = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let is_big = reactor
+        .create_compute(&[CellId::Input(input)], |v| (v[0] > 10) as i32)
+        .unwrap();
+    let doubled = reactor
+        .create_compute(&[CellId::Compute(plus_one)], |v| v[0] * 2)
+        .unwrap();
+    assert!(reactor
+        .add_callback(plus_one, |v| cb1.callback_called(v))
+        .is_some());
+    assert!(reactor
+        .add_callback(is_big, |v| cb2.callback_called(v))
+        .is_some());
+    assert!(reactor
+        .add_callback(doubled, |v| cb3.callback_called(v))
+        .is_some());
+
+    assert_eq!(
+        reactor.set_value_report(input, 2),
+        Some(vec![plus_one, doubled])
+    );
+    cb1.expect_to_have_been_called_with(3);
+    cb2.expect_not_to_have_been_called();
+    cb3.expect_to_have_been_called_with(6);
+
+    assert_eq!(
+        reactor.set_value_report(input, 20),
+        Some(vec![plus_one, is_big, doubled])
+    );
+    cb1.expect_to_have_been_called_with(21);
+    cb2.expect_to_have_been_called_with(1);
+    cb3.expect_to_have_been_called_with(42);
+
+    assert_eq!(reactor.set_value_report(input, 20), Some(vec![]));
+    assert_eq!(Reactor::new().set_value_report(input, 0), None);
+}
+
+#[test]
+fn weak_callbacks_stop_firing_once_dropped() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let owner: Rc<RefCell<dyn FnMut(i32)>> = Rc::new(RefCell::new(|v| cb.callback_called(v)));
+    assert!(reactor
+        .add_weak_callback(output, Rc::downgrade(&owner))
+        .is_some());
+
+    assert!(reactor.set_value(input, 2));
+    cb.expect_to_have_been_called_with(3);
+
+    drop(owner);
+    assert!(reactor.set_value(input, 3));
+    cb.expect_not_to_have_been_called();
+    assert_eq!(reactor.callback_count(output), 0);
+}
+
+#[test]
+fn debounced_writes_propagate_once_per_flush() {
+    let calls = std::cell::Cell::new(0);
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor
+        .add_callback(output, |v| {
+            calls.set(calls.get() + 1);
+            cb.callback_called(v)
+        })
+        .is_some());
+
+    let mut debounced = DebouncedReactor::new(&mut reactor);
+    assert!(debounced.set_value(input, 2));
+    assert!(debounced.set_value(input, 3));
+    assert!(debounced.set_value(input, 4));
+    assert_eq!(debounced.pending_len(), 1);
+    assert_eq!(calls.get(), 0);
+    assert_eq!(debounced.flush(), vec![output]);
+    assert_eq!(calls.get(), 1);
+    cb.expect_to_have_been_called_with(5);
+    drop(debounced);
+    assert_eq!(reactor.value(CellId::Compute(output)), Some(5));
+}
+
+#[test]
+fn debounced_writes_flush_after_max_pending() {
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(1);
+    let b = reactor.create_input(1);
+    let sum = reactor
+        .create_compute(&[CellId::Input(a), CellId::Input(b)], |v| v[0] + v[1])
+        .unwrap();
+    assert!(reactor
+        .add_callback(sum, |v| cb.callback_called(v))
+        .is_some());
+
+    let mut debounced = DebouncedReactor::with_max_pending(&mut reactor, 2);
+    assert!(debounced.set_value(a, 10));
+    cb.expect_not_to_have_been_called();
+    assert!(debounced.set_value(b, 20));
+    cb.expect_to_have_been_called_with(30);
+    assert!(debounced.set_value(a, 5));
+    drop(debounced);
+    cb.expect_to_have_been_called_with(25);
+
+    let mut dummy_reactor = Reactor::new();
+    let dummy_input = dummy_reactor.create_input(1);
+    assert!(!DebouncedReactor::new(&mut Reactor::new()).set_value(dummy_input, 1));
+}
+
+#[test]
+fn float_epsilon_suppresses_insignificant_changes() {
+    let calls = std::cell::Cell::new(0);
+    let mut reactor = Reactor::<f64>::with_float_epsilon(1e-9);
+    let a = reactor.create_input(0.1);
+    let b = reactor.create_input(0.2);
+    let sum = reactor
+        .create_compute(&[CellId::Input(a), CellId::Input(b)], |v| v[0] + v[1])
+        .unwrap();
+    assert!(reactor
+        .add_callback(sum, |_| calls.set(calls.get() + 1))
+        .is_some());
+
+    assert!(reactor.set_value(a, 0.1 + 1e-12));
+    assert_eq!(calls.get(), 0);
+    assert!(reactor.set_value(b, 0.5));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn float_epsilon_lets_small_changes_add_up() {
+    let calls = std::cell::Cell::new(0);
+    let mut reactor = Reactor::<f64>::with_float_epsilon(1e-9);
+    let input = reactor.create_input(0.0);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1.0)
+        .unwrap();
+    assert!(reactor
+        .add_callback(plus_one, |_| calls.set(calls.get() + 1))
+        .is_some());
+
+    assert!(reactor.set_value(input, 4e-10));
+    assert!(reactor.set_value(input, 8e-10));
+    assert_eq!(calls.get(), 0);
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(1.0));
+
+    assert!(reactor.set_value(input, 1.2e-9));
+    assert_eq!(calls.get(), 1);
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(1.0 + 1.2e-9));
+}
+
+#[test]
+fn float_epsilon_for_single_precision() {
+    let calls = std::cell::Cell::new(0);
+    let mut reactor = Reactor::<f32>::with_float_epsilon(1e-3);
+    let input = reactor.create_input(1.0);
+    let halved = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] / 2.0)
+        .unwrap();
+    assert!(reactor
+        .add_callback(halved, |_| calls.set(calls.get() + 1))
+        .is_some());
+
+    assert!(reactor.set_value(input, 1.0001));
+    assert_eq!(calls.get(), 0);
+    assert!(reactor.set_value(input, 3.0));
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn profiling_counts_recomputations() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let times_two = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 2)
+        .unwrap();
+    let join = reactor
+        .create_compute(
+            &[CellId::Compute(plus_one), CellId::Compute(times_two)],
+            |v| v[0] + v[1],
+        )
+        .unwrap();
+    assert_eq!(reactor.recompute_count(join), None);
+
+    reactor.enable_profiling();
+    assert_eq!(reactor.recompute_count(join), Some(0));
+    assert!(reactor.set_value(input, 2));
+    assert!(reactor.set_value(input, 3));
+    assert_eq!(reactor.recompute_count(plus_one), Some(2));
+    assert_eq!(reactor.recompute_count(join), Some(2));
+    assert_eq!(reactor.value(CellId::Compute(join)), Some(10));
+}
+
+#[test]
+fn compute_cells_can_be_created_in_a_batch() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let specs: Vec<(Vec<BatchDep>, ComputeFn<i32>)> = vec![
+        (
+            vec![BatchDep::Cell(CellId::Input(input))],
+            Box::new(|v| v[0] + 1),
+        ),
+        (vec![BatchDep::Earlier(0)], Box::new(|v| v[0] * 10)),
+        (
+            vec![BatchDep::Earlier(0), BatchDep::Earlier(1)],
+            Box::new(|v| v[0] + v[1]),
+        ),
+    ];
+    let ids = reactor.create_compute_many(specs).unwrap();
+    assert_eq!(ids.len(), 3);
+    assert_eq!(reactor.value(CellId::Compute(ids[1])), Some(20));
+    assert_eq!(reactor.value(CellId::Compute(ids[2])), Some(22));
+    assert!(reactor.set_value(input, 2));
+    assert_eq!(reactor.value(CellId::Compute(ids[2])), Some(33));
+}
+
+#[test]
+fn failed_batches_create_no_cells() {
+    let mut dummy_reactor = Reactor::new();
+    let dummy_input = dummy_reactor.create_input(1);
+
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let specs: Vec<(Vec<BatchDep>, ComputeFn<i32>)> = vec![
+        (
+            vec![BatchDep::Cell(CellId::Input(input))],
+            Box::new(|v| v[0] + 1),
+        ),
+        (vec![BatchDep::Earlier(1)], Box::new(|v| v[0])),
+        (
+            vec![BatchDep::Cell(CellId::Input(dummy_input))],
+            Box::new(|v| v[0]),
+        ),
+    ];
+    assert_eq!(reactor.create_compute_many(specs), Err(1));
+    assert!(reactor.compute_ids().is_empty());
+
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert_eq!(plus_one, reactor.compute_ids()[0]);
+    assert!(reactor.set_value(input, 5));
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(6));
+}
+
+#[test]
+fn batches_resolve_earlier_specs_regardless_of_cells_created_in_between() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let specs: Vec<(Vec<BatchDep>, ComputeFn<i32>)> = vec![
+        (
+            vec![BatchDep::Cell(CellId::Input(input))],
+            Box::new(|v| v[0] + 1),
+        ),
+        (vec![BatchDep::Earlier(0)], Box::new(|v| v[0] * 10)),
+    ];
+    let unrelated = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] - 100)
+        .unwrap();
+    let ids = reactor.create_compute_many(specs).unwrap();
+    assert_ne!(ids[0], unrelated);
+    assert_eq!(reactor.value(CellId::Compute(ids[1])), Some(20));
+}
+
+#[test]
+fn builder_configures_the_reactor() {
+    let calls = std::cell::Cell::new(0);
+    let mut reactor = ReactorBuilder::new()
+        .with_profiling()
+        .with_float_epsilon(1e-9)
+        .build();
+    let input = reactor.create_input(1.0);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 2.0)
+        .unwrap();
+    assert!(reactor
+        .add_callback(output, |_| calls.set(calls.get() + 1))
+        .is_some());
+    assert_eq!(reactor.recompute_count(output), Some(0));
+
+    assert!(reactor.set_value(input, 1.0 + 1e-12));
+    assert_eq!(calls.get(), 0);
+    assert!(reactor.set_value(input, 2.0));
+    assert_eq!(calls.get(), 1);
+    assert_eq!(reactor.recompute_count(output), Some(2));
+}
+
+#[test]
+fn builder_can_enable_recording() {
+    let mut reactor = ReactorBuilder::new().with_recording().build();
+    let input = reactor.create_input(1);
+    assert!(reactor.set_value(input, 2));
+    assert_eq!(reactor.event_log(), &[(input, 2)]);
+}
+
+#[test]
+fn inspect_describes_each_kind_of_cell() {
+    let mut dummy_reactor = Reactor::new();
+    let dummy_input = dummy_reactor.create_input(1);
+
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert_eq!(
+        reactor.inspect(CellId::Input(dummy_input)),
+        CellStatus::Missing
+    );
+    assert_eq!(reactor.inspect(CellId::Input(input)), CellStatus::Input(1));
+    assert_eq!(
+        reactor.inspect(CellId::Compute(output)),
+        CellStatus::Compute {
+            value: 2,
+            dirty: false
+        }
+    );
+}
+
+#[test]
+fn frozen_compute_cells_are_dirty_after_an_input_change() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor.freeze(output));
+    assert!(reactor.set_value(input, 5));
+    assert_eq!(
+        reactor.inspect(CellId::Compute(output)),
+        CellStatus::Compute {
+            value: 2,
+            dirty: true
+        }
+    );
+
+    assert!(reactor.unfreeze(output));
+    assert_eq!(
+        reactor.inspect(CellId::Compute(output)),
+        CellStatus::Compute {
+            value: 6,
+            dirty: false
+        }
+    );
+}
+
+#[test]
+fn callbacks_can_be_removed_by_tag() {
+    let first = std::cell::Cell::new(0);
+    let second = std::cell::Cell::new(0);
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor
+        .add_callback_tagged(output, 7, |_| first.set(first.get() + 1))
+        .is_some());
+    assert!(reactor
+        .add_callback_tagged(output, 7, |_| second.set(second.get() + 1))
+        .is_some());
+    assert!(reactor
+        .add_callback_tagged(output, 8, |v| cb.callback_called(v))
+        .is_some());
+
+    assert_eq!(reactor.remove_callbacks_by_tag(output, 7), 2);
+    assert_eq!(reactor.remove_callbacks_by_tag(output, 7), 0);
+    assert!(reactor.set_value(input, 2));
+    assert_eq!(first.get(), 0);
+    assert_eq!(second.get(), 0);
+    cb.expect_to_have_been_called_with(3);
+    assert_eq!(reactor.callback_count(output), 1);
+}
+
+#[test]
+fn asymmetric_diamonds_recompute_the_join_once() {
+    let joins = std::cell::Cell::new(0);
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let short = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 10)
+        .unwrap();
+    let long_1 = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let long_2 = reactor
+        .create_compute(&[CellId::Compute(long_1)], |v| v[0] + 1)
+        .unwrap();
+    let long_3 = reactor
+        .create_compute(&[CellId::Compute(long_2)], |v| v[0] + 1)
+        .unwrap();
+    let join = reactor
+        .create_compute(&[CellId::Compute(short), CellId::Compute(long_3)], |v| {
+            joins.set(joins.get() + 1);
+            v[0] + v[1]
+        })
+        .unwrap();
+    reactor.enable_profiling();
+
+    let before = joins.get();
+    assert!(reactor.set_value(input, 2));
+    assert_eq!(joins.get(), before + 1);
+    assert_eq!(reactor.recompute_count(join), Some(1));
+    assert_eq!(reactor.value(CellId::Compute(join)), Some(25));
+}
+
+#[test]
+fn path_between_direct_dependencies() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert_eq!(
+        reactor.path_between(CellId::Input(input), output),
+        Some(vec![CellId::Input(input), CellId::Compute(output)])
+    );
+}
+
+#[test]
+fn path_between_takes_the_shortest_route() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let a = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let b = reactor
+        .create_compute(&[CellId::Compute(a)], |v| v[0] + 1)
+        .unwrap();
+    let c = reactor
+        .create_compute(&[CellId::Compute(b)], |v| v[0] + 1)
+        .unwrap();
+    let output = reactor
+        .create_compute(&[CellId::Compute(c), CellId::Compute(a)], |v| v[0] + v[1])
+        .unwrap();
+    assert_eq!(
+        reactor.path_between(CellId::Input(input), output),
+        Some(vec![
+            CellId::Input(input),
+            CellId::Compute(a),
+            CellId::Compute(output)
+        ])
+    );
+}
+
+#[test]
+fn path_between_unrelated_cells_is_none() {
+    let mut reactor = Reactor::new();
+    let first = reactor.create_input(1);
+    let second = reactor.create_input(2);
+    let output = reactor
+        .create_compute(&[CellId::Input(second)], |v| v[0] + 1)
+        .unwrap();
+    assert_eq!(reactor.path_between(CellId::Input(first), output), None);
+    assert_eq!(reactor.path_between(CellId::Compute(output), output), None);
+}
+
+#[test]
+fn fixed_arity_compute_cells() {
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(10);
+    let b = reactor.create_input(3);
+    let difference = reactor
+        .create_compute_n([CellId::Input(a), CellId::Input(b)], |[x, y]| x - y)
+        .unwrap();
+    assert_eq!(reactor.value(CellId::Compute(difference)), Some(7));
+    assert!(reactor.set_value(b, 4));
+    assert_eq!(reactor.value(CellId::Compute(difference)), Some(6));
+}
+
+#[test]
+fn values_of_several_cells() {
+    let mut dummy_reactor = Reactor::new();
+    let dummy_input = dummy_reactor.create_input(1);
+
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert_eq!(
+        reactor.values_of(&[
+            CellId::Input(input),
+            CellId::Compute(output),
+            CellId::Input(dummy_input)
+        ]),
+        vec![Some(1), Some(2), None]
+    );
+}
+
+#[test]
+fn cells_with_dependents_are_not_safe_to_remove() {
+    let mut dummy_reactor = Reactor::new();
+    let dummy_input = dummy_reactor.create_input(1);
+
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert!(!reactor.safe_to_remove(CellId::Input(input)));
+    assert!(reactor.safe_to_remove(CellId::Compute(output)));
+    assert!(reactor.safe_to_remove(CellId::Input(dummy_input)));
+}
+
+#[test]
+fn recompute_all_runs_no_callbacks() {
+    let calls = std::cell::Cell::new(0);
+    let offset = std::rc::Rc::new(std::cell::Cell::new(1));
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_offset = {
+        let offset = offset.clone();
+        reactor
+            .create_compute(&[CellId::Input(input)], move |v| v[0] + offset.get())
+            .unwrap()
+    };
+    let doubled = reactor
+        .create_compute(&[CellId::Compute(plus_offset)], |v| v[0] * 2)
+        .unwrap();
+    assert!(reactor
+        .add_callback(doubled, |_| calls.set(calls.get() + 1))
+        .is_some());
+
+    offset.set(10);
+    reactor.recompute_all();
+    assert_eq!(calls.get(), 0);
+    assert_eq!(reactor.value(CellId::Compute(plus_offset)), Some(11));
+    assert_eq!(reactor.value(CellId::Compute(doubled)), Some(22));
+}
+
+#[test]
+fn overridden_compute_cells_keep_their_value() {
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let doubled = reactor
+        .create_compute(&[CellId::Compute(plus_one)], |v| v[0] * 2)
+        .unwrap();
+    assert!(reactor
+        .add_callback(doubled, |v| cb.callback_called(v))
+        .is_some());
+
+    assert!(reactor.override_value(plus_one, 10));
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(10));
+    assert_eq!(reactor.value(CellId::Compute(doubled)), Some(20));
+    cb.expect_to_have_been_called_with(20);
+
+    assert!(reactor.set_value(input, 5));
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(10));
+    cb.expect_not_to_have_been_called();
+}
+
+#[test]
+fn clearing_an_override_restores_normal_behavior() {
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor
+        .add_callback(plus_one, |v| cb.callback_called(v))
+        .is_some());
+    assert!(reactor.override_value(plus_one, 10));
+    cb.expect_to_have_been_called_with(10);
+    assert!(reactor.set_value(input, 5));
+
+    assert!(reactor.clear_override(plus_one));
+    cb.expect_to_have_been_called_with(6);
+    assert!(reactor.set_value(input, 7));
+    cb.expect_to_have_been_called_with(8);
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(8));
+}
+
+#[test]
+fn merged_reactors_keep_updating_under_new_ids() {
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+
+    let mut other = Reactor::new();
+    let other_input = other.create_input(2);
+    let times_two = other
+        .create_compute(&[CellId::Input(other_input)], |v| v[0] * 2)
+        .unwrap();
+    let times_three = other
+        .create_compute(&[CellId::Input(other_input)], |v| v[0] * 3)
+        .unwrap();
+    assert!(other
+        .add_callback(times_two, |v| cb.callback_called(v))
+        .is_some());
+
+    let mut closures: std::collections::HashMap<ComputeCellId, ComputeFn<i32>> =
+        std::collections::HashMap::new();
+    closures.insert(times_three, Box::new(|v| v[0] * 30));
+    let remap = reactor.merge(other, closures);
+    assert_eq!(remap.len(), 3);
+    let Some(&CellId::Input(new_input)) = remap.get(&CellId::Input(other_input)) else {
+        panic!("the input cell was not imported");
+    };
+    let Some(&CellId::Compute(new_times_two)) = remap.get(&CellId::Compute(times_two)) else {
+        panic!("the compute cell was not imported");
+    };
+    let Some(&CellId::Compute(new_times_three)) = remap.get(&CellId::Compute(times_three)) else {
+        panic!("the compute cell was not imported");
+    };
+    assert_eq!(reactor.value(CellId::Compute(new_times_three)), Some(60));
+
+    assert!(reactor.set_value(input, 5));
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(6));
+    cb.expect_not_to_have_been_called();
+    assert!(reactor.set_value(new_input, 4));
+    cb.expect_to_have_been_called_with(8);
+    assert_eq!(reactor.value(CellId::Compute(new_times_two)), Some(8));
+    assert_eq!(reactor.value(CellId::Compute(new_times_three)), Some(120));
+}
+
+#[test]
+fn leaf_compute_cells_only_depend_on_inputs() {
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(1);
+    let b = reactor.create_input(2);
+    let sum = reactor
+        .create_compute(&[CellId::Input(a), CellId::Input(b)], |v| v[0] + v[1])
+        .unwrap();
+    let doubled = reactor
+        .create_compute(&[CellId::Input(b)], |v| v[0] * 2)
+        .unwrap();
+    reactor
+        .create_compute(&[CellId::Compute(sum), CellId::Input(a)], |v| v[0] + v[1])
+        .unwrap();
+    reactor
+        .create_compute(&[CellId::Compute(doubled)], |v| v[0] + 1)
+        .unwrap();
+    assert_eq!(reactor.leaf_compute_cells(), vec![sum, doubled]);
+}
+
+#[test]
+fn cached_values_change_only_when_recomputed() {
+    let offset = std::rc::Rc::new(std::cell::Cell::new(1));
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_offset = {
+        let offset = offset.clone();
+        reactor
+            .create_compute(&[CellId::Input(input)], move |v| v[0] + offset.get())
+            .unwrap()
+    };
+    assert_eq!(reactor.cached_value(plus_offset), Some(2));
+
+    offset.set(10);
+    assert_eq!(reactor.cached_value(plus_offset), Some(2));
+    reactor.recompute_all();
+    assert_eq!(reactor.cached_value(plus_offset), Some(11));
+}
+
+#[test]
+fn checked_writes_refuse_non_finite_values() {
+    let mut dummy_reactor = Reactor::<f64>::new();
+    let dummy_input = dummy_reactor.create_input(1.0);
+
+    let mut reactor = Reactor::<f64>::new();
+    let input = reactor.create_input(1.0);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 2.0)
+        .unwrap();
+    assert_eq!(
+        reactor.set_value_checked(input, f64::NAN),
+        Err(SetValueError::NotFinite)
+    );
+    assert_eq!(reactor.value(CellId::Input(input)), Some(1.0));
+    assert_eq!(reactor.set_value_checked(input, 3.0), Ok(()));
+    assert_eq!(reactor.value(CellId::Compute(output)), Some(6.0));
+    assert_eq!(
+        reactor.set_value_checked(dummy_input, 3.0),
+        Err(SetValueError::NonexistentCell)
+    );
+}
+
+#[test]
+fn fan_in_can_be_limited() {
+    let mut reactor = Reactor::new();
+    let inputs = (0..4)
+        .map(|i| CellId::Input(reactor.create_input(i)))
+        .collect::<Vec<_>>();
+    assert!(reactor.create_compute(&inputs, |v| v.len() as i32).is_ok());
+
+    reactor.set_max_fan_in(3);
+    assert_eq!(
+        reactor.create_compute(&inputs, |v| v.len() as i32),
+        Err(CreateComputeError::TooManyDependencies { limit: 3, got: 4 })
+    );
+    assert!(reactor
+        .create_compute(&inputs[..3], |v| v.len() as i32)
+        .is_ok());
+}
+
+#[test]
+fn all_callbacks_can_be_cleared_at_once() {
+    let calls = std::cell::Cell::new(0);
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let times_two = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 2)
+        .unwrap();
+    assert!(reactor
+        .add_callback(plus_one, |_| calls.set(calls.get() + 1))
+        .is_some());
+    assert!(reactor
+        .add_callback(plus_one, |_| calls.set(calls.get() + 1))
+        .is_some());
+    assert!(reactor
+        .add_callback(times_two, |_| calls.set(calls.get() + 1))
+        .is_some());
+
+    assert_eq!(reactor.clear_all_callbacks(), 3);
+    assert!(reactor.set_value(input, 5));
+    assert_eq!(calls.get(), 0);
+    assert_eq!(reactor.clear_all_callbacks(), 0);
+}
+
+#[test]
+fn observed_cells_are_those_with_callbacks() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let times_two = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 2)
+        .unwrap();
+    reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] - 1)
+        .unwrap();
+    let first = reactor.add_callback(plus_one, |_| ()).unwrap();
+    let second = reactor.add_callback(plus_one, |_| ()).unwrap();
+    let third = reactor.add_callback(times_two, |_| ()).unwrap();
+    assert_eq!(reactor.observed_cells(), vec![plus_one, times_two]);
+
+    assert!(reactor.remove_callback(times_two, third).is_ok());
+    assert_eq!(reactor.observed_cells(), vec![plus_one]);
+    assert!(reactor.remove_callback(plus_one, first).is_ok());
+    assert_eq!(reactor.observed_cells(), vec![plus_one]);
+    assert!(reactor.remove_callback(plus_one, second).is_ok());
+    assert!(reactor.observed_cells().is_empty());
+}
+
+#[test]
+fn subgraph_snapshots_only_capture_reachable_cells() {
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(1);
+    let b = reactor.create_input(2);
+    let unrelated_input = reactor.create_input(3);
+    let sum = reactor
+        .create_compute(&[CellId::Input(a), CellId::Input(b)], |v| v[0] + v[1])
+        .unwrap();
+    let doubled = reactor
+        .create_compute(&[CellId::Compute(sum)], |v| v[0] * 2)
+        .unwrap();
+    let mut unrelated = reactor
+        .create_compute(&[CellId::Input(unrelated_input)], |v| v[0] + 1)
+        .unwrap();
+    for _ in 0..10 {
+        unrelated = reactor
+            .create_compute(&[CellId::Compute(unrelated), CellId::Input(b)], |v| {
+                v[0] + v[1]
+            })
+            .unwrap();
+    }
+    let root = reactor
+        .create_compute(&[CellId::Compute(doubled), CellId::Input(a)], |v| {
+            v[0] - v[1]
+        })
+        .unwrap();
+
+    let state = reactor.snapshot_subgraph(&[root]);
+    assert_eq!(state.inputs(), &[(a, 1), (b, 2)]);
+    assert_eq!(state.computes(), &[(sum, 3), (doubled, 6), (root, 5)]);
+}
+
+#[test]
+fn active_dependencies_of_a_select_cell() {
+    let mut reactor = Reactor::new();
+    let selector = reactor.create_input(1);
+    let first = reactor.create_input(5);
+    let second = reactor.create_input(7);
+    let select = reactor
+        .create_compute(
+            &[
+                CellId::Input(selector),
+                CellId::Input(first),
+                CellId::Input(second),
+            ],
+            |v| if v[0] != 0 { v[1] } else { v[2] },
+        )
+        .unwrap();
+    assert_eq!(
+        reactor.active_dependencies(select),
+        vec![CellId::Input(selector), CellId::Input(first)]
+    );
+
+    assert!(reactor.set_value(selector, 0));
+    assert_eq!(
+        reactor.active_dependencies(select),
+        vec![CellId::Input(selector), CellId::Input(second)]
+    );
+}
+
+#[test]
+fn recent_log_keeps_only_the_latest_writes() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(0);
+    reactor.enable_recent_log(2);
+    for value in 1..=5 {
+        assert!(reactor.set_value(input, value));
+    }
+    assert_eq!(reactor.recent_log(), &[(input, 4), (input, 5)]);
+    assert!(reactor.event_log().is_empty());
+}
+
+#[test]
+fn builder_can_enable_the_recent_log() {
+    let mut reactor = ReactorBuilder::new().with_recent_log(1).build();
+    let input = reactor.create_input(0);
+    assert!(reactor.set_value(input, 1));
+    assert!(reactor.set_value(input, 2));
+    assert_eq!(reactor.recent_log(), &[(input, 2)]);
+}
+
+#[test]
+fn degrees_of_a_diamond() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let times_two = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 2)
+        .unwrap();
+    let join = reactor
+        .create_compute(
+            &[CellId::Compute(plus_one), CellId::Compute(times_two)],
+            |v| v[0] + v[1],
+        )
+        .unwrap();
+
+    let degrees = reactor.degrees();
+    assert_eq!(degrees.len(), 4);
+    assert_eq!(degrees[&CellId::Input(input)], (0, 2));
+    assert_eq!(degrees[&CellId::Compute(plus_one)], (1, 1));
+    assert_eq!(degrees[&CellId::Compute(join)], (2, 0));
+}
+
+#[test]
+fn estimated_size_grows_with_the_graph() {
+    let mut reactor = Reactor::new();
+    let empty = reactor.estimated_size_bytes();
+    let input = reactor.create_input(1);
+    let with_input = reactor.estimated_size_bytes();
+    assert!(with_input > empty);
+
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let with_compute = reactor.estimated_size_bytes();
+    assert!(with_compute > with_input);
+
+    assert!(reactor.add_callback(output, |_| ()).is_some());
+    assert!(reactor.estimated_size_bytes() > with_compute);
+}
+
+#[test]
+fn callbacks_can_defer_writes_to_the_same_reactor() {
+    let calls = std::cell::Cell::new(0);
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(1);
+    let b = reactor.create_input(0);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(a)], |v| v[0] + 1)
+        .unwrap();
+    let times_two = reactor
+        .create_compute(&[CellId::Input(b)], |v| v[0] * 2)
+        .unwrap();
+    let writer = reactor.deferred_writer();
+    let calls_ref = &calls;
+    assert!(reactor
+        .add_callback(plus_one, move |v| {
+            calls_ref.set(calls_ref.get() + 1);
+            writer.set_value(b, v * 10);
+        })
+        .is_some());
+    assert!(reactor
+        .add_callback(times_two, |v| cb.callback_called(v))
+        .is_some());
+
+    assert_eq!(
+        reactor.set_value_report(a, 2),
+        Some(vec![plus_one, times_two])
+    );
+    assert_eq!(reactor.value(CellId::Input(b)), Some(30));
+    assert_eq!(reactor.value(CellId::Compute(times_two)), Some(60));
+    cb.expect_to_have_been_called_with(60);
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn deferred_writes_can_run_a_callback_again_in_the_same_set_value() {
+    let values = std::cell::RefCell::new(Vec::new());
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let plus_one = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let writer = reactor.deferred_writer();
+    let values_ref = &values;
+    assert!(reactor
+        .add_callback(plus_one, move |v| {
+            values_ref.borrow_mut().push(v);
+            if v < 5 {
+                writer.set_value(input, v);
+            }
+        })
+        .is_some());
+
+    assert_eq!(reactor.set_value_report(input, 2), Some(vec![plus_one]));
+    assert_eq!(*values.borrow(), [3, 4, 5]);
+    assert_eq!(reactor.value(CellId::Compute(plus_one)), Some(5));
+}
+
+#[test]
+fn average_cells_follow_their_dependencies() {
+    let mut reactor = Reactor::<f64>::new();
+    let a = reactor.create_input(1.0);
+    let b = reactor.create_input(2.0);
+    let c = reactor.create_input(6.0);
+    let average = reactor
+        .create_average(&[CellId::Input(a), CellId::Input(b), CellId::Input(c)])
+        .unwrap();
+    assert_eq!(reactor.value(CellId::Compute(average)), Some(3.0));
+    assert!(reactor.set_value(c, 9.0));
+    assert_eq!(reactor.value(CellId::Compute(average)), Some(4.0));
+}
+
+#[test]
+fn integer_averages_are_truncated() {
+    let mut reactor = Reactor::new();
+    let a = reactor.create_input(1);
+    let b = reactor.create_input(2);
+    let average = reactor
+        .create_average(&[CellId::Input(a), CellId::Input(b)])
+        .unwrap();
+    assert_eq!(reactor.value(CellId::Compute(average)), Some(1));
+    assert!(reactor.set_value(b, 4));
+    assert_eq!(reactor.value(CellId::Compute(average)), Some(2));
+}
+
+#[test]
+fn averages_of_nothing_are_rejected() {
+    let mut reactor = Reactor::<i32>::new();
+    assert_eq!(
+        reactor.create_average(&[]),
+        Err(CreateComputeError::EmptyDependencies)
+    );
+}
+
+#[test]
+fn compute_cells_can_be_found_by_value() {
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(2);
+    let plus_two = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 2)
+        .unwrap();
+    let doubled = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 2)
+        .unwrap();
+    let squared = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * v[0])
+        .unwrap();
+    reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] - 1)
+        .unwrap();
+    assert_eq!(
+        reactor.cells_with_value(4),
+        vec![plus_two, doubled, squared]
+    );
+    assert!(reactor.cells_with_value(100).is_empty());
+
+    assert!(reactor.set_value(input, 3));
+    assert_eq!(reactor.cells_with_value(6), vec![doubled]);
+}
+
+#[test]
+fn callbacks_until_a_threshold_is_reached() {
+    let cb = CallbackRecorder::new();
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let times_ten = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] * 10)
+        .unwrap();
+    assert!(reactor
+        .add_callback_until(times_ten, |v| v >= 100, |v| cb.callback_called(v))
+        .is_some());
+
+    assert!(reactor.set_value(input, 5));
+    cb.expect_to_have_been_called_with(50);
+    assert!(reactor.set_value(input, 12));
+    cb.expect_to_have_been_called_with(120);
+    assert_eq!(reactor.callback_count(times_ten), 0);
+    assert!(reactor.set_value(input, 3));
+    cb.expect_not_to_have_been_called();
+}
+
+#[test]
+fn state_strings_are_deterministic() {
+    fn build() -> (Reactor<'static, i32>, InputCellId) {
+        let mut reactor = Reactor::new();
+        let a = reactor.create_input(1);
+        let b = reactor.create_input(2);
+        let sum = reactor
+            .create_compute(&[CellId::Input(a), CellId::Input(b)], |v| v[0] + v[1])
+            .unwrap();
+        reactor
+            .create_compute(&[CellId::Compute(sum), CellId::Input(a)], |v| v[0] * v[1])
+            .unwrap();
+        (reactor, a)
+    }
+
+    let (first, _) = build();
+    let (mut second, a) = build();
+    assert_eq!(
+        first.to_state_string(),
+        "input 1: 1\ninput 2: 2\ncompute 3: 3 <- input 1, input 2\ncompute 4: 3 <- compute 3, input 1\n"
+    );
+    assert_eq!(first.to_state_string(), second.to_state_string());
+
+    assert!(second.set_value(a, 5));
+    assert_ne!(first.to_state_string(), second.to_state_string());
+}
+
+#[test]
+fn averages_of_too_many_cells_are_rejected() {
+    let mut reactor = Reactor::<i32>::new();
+    let input = CellId::Input(reactor.create_input(1));
+    let dependencies = vec![input; u16::MAX as usize + 1];
+    assert_eq!(
+        reactor.create_average(&dependencies),
+        Err(CreateComputeError::TooManyToAverage {
+            got: u16::MAX as usize + 1
+        })
+    );
+}
+
+#[test]
+fn dropped_weak_callbacks_are_not_reported_as_live() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    let strong = reactor.add_callback(output, |_| ()).unwrap();
+    let owner: Rc<RefCell<dyn FnMut(i32)>> = Rc::new(RefCell::new(|_| ()));
+    assert!(reactor
+        .add_weak_callback(output, Rc::downgrade(&owner))
+        .is_some());
+    assert_eq!(reactor.callback_count(output), 2);
+
+    drop(owner);
+    assert_eq!(reactor.callback_count(output), 1);
+    assert_eq!(reactor.callback_ids(output), vec![strong]);
+    assert!(reactor.remove_callback(output, strong).is_ok());
+    assert!(reactor.observed_cells().is_empty());
+}
+
+#[test]
+fn deferred_writers_keep_working_after_a_merge() {
+    let mut reactor = Reactor::new();
+    reactor.create_input(0);
+
+    let mut other = Reactor::new();
+    let a = other.create_input(1);
+    let b = other.create_input(0);
+    let plus_one = other
+        .create_compute(&[CellId::Input(a)], |v| v[0] + 1)
+        .unwrap();
+    let times_two = other
+        .create_compute(&[CellId::Input(b)], |v| v[0] * 2)
+        .unwrap();
+    let writer = other.deferred_writer();
+    assert!(other
+        .add_callback(plus_one, move |v| writer.set_value(b, v * 10))
+        .is_some());
+
+    let remap = reactor.merge(other, std::collections::HashMap::new());
+    let Some(&CellId::Input(new_a)) = remap.get(&CellId::Input(a)) else {
+        panic!("the input cell was not imported");
+    };
+    let new_times_two = remap[&CellId::Compute(times_two)];
+    assert!(reactor.set_value(new_a, 2));
+    assert_eq!(reactor.value(remap[&CellId::Input(b)]), Some(30));
+    assert_eq!(reactor.value(new_times_two), Some(60));
+}
+
+#[test]
+fn clearing_callbacks_does_not_count_dropped_weak_callbacks() {
+    use std::{cell::RefCell, rc::Rc};
+
+    let mut reactor = Reactor::new();
+    let input = reactor.create_input(1);
+    let output = reactor
+        .create_compute(&[CellId::Input(input)], |v| v[0] + 1)
+        .unwrap();
+    assert!(reactor.add_callback(output, |_| ()).is_some());
+    let owner: Rc<RefCell<dyn FnMut(i32)>> = Rc::new(RefCell::new(|_| ()));
+    assert!(reactor
+        .add_weak_callback(output, Rc::downgrade(&owner))
+        .is_some());
+
+    drop(owner);
+    assert_eq!(reactor.clear_all_callbacks(), 1);
+    assert_eq!(reactor.callback_count(output), 0);
+}
@@ -1,9 +1,19 @@
 // The code below is a stub. Just enough to satisfy the compiler.
 // In order to pass the tests you can add-to or change any of this code.
 
-const EARTH_YEAR_IN_SECONDS: u64 = 31557600;
+use std::{
+    cmp::Ordering,
+    fmt,
+    hash::{Hash, Hasher},
+    iter::Sum,
+    ops::{Add, Div, Mul, Sub},
+    str::FromStr,
+};
 
-#[derive(Debug)]
+/// The length of an Earth year (365.25 days) in seconds.
+pub const EARTH_YEAR_IN_SECONDS: u64 = 31557600;
+
+#[derive(Clone, Copy, Debug)]
 pub struct Duration {
     earth_year: f64,
 }
@@ -15,12 +25,461 @@ impl From<u64> for Duration {
     }
 }
 
+/// Converts back to whole seconds, rounded to the nearest second.
+impl From<Duration> for u64 {
+    fn from(d: Duration) -> Self {
+        d.as_seconds()
+    }
+}
+
+impl Default for Duration {
+    fn default() -> Self {
+        Duration::ZERO
+    }
+}
+
+impl From<std::time::Duration> for Duration {
+    fn from(d: std::time::Duration) -> Self {
+        let earth_year = d.as_secs_f64() / EARTH_YEAR_IN_SECONDS as f64;
+        Self { earth_year }
+    }
+}
+
+/// A `Duration` backed by single-precision floats, for targets where `f64` math is costly.
+#[derive(Debug)]
+pub struct Duration32 {
+    earth_year: f32,
+}
+
+impl From<u64> for Duration32 {
+    fn from(s: u64) -> Self {
+        let earth_year = s as f32 / EARTH_YEAR_IN_SECONDS as f32;
+        Self { earth_year }
+    }
+}
+
+impl Duration {
+    pub const ZERO: Duration = Duration { earth_year: 0.0 };
+
+    // Clamps negative values, negative zero and `NaN` to `ZERO`, so that every way of reaching a
+    // zero duration yields one equal to `ZERO`.
+    fn non_negative(earth_year: f64) -> Self {
+        if earth_year > 0.0 {
+            Self { earth_year }
+        } else {
+            Self::ZERO
+        }
+    }
+
+    /// Builds the duration elapsed from `start_secs` to `end_secs`. An end before the start yields
+    /// a zero duration.
+    pub fn between(start_secs: u64, end_secs: u64) -> Self {
+        Duration::from(end_secs.saturating_sub(start_secs))
+    }
+
+    /// Builds a duration from a number of Earth years. Negative and non-finite values are clamped
+    /// to zero.
+    pub fn from_earth_years(years: f64) -> Self {
+        Self::try_from_earth_years(years).unwrap_or(Self::ZERO)
+    }
+
+    /// Builds a duration from a number of Earth years, rejecting `NaN` and infinite values.
+    /// Negative values are clamped to zero.
+    pub fn try_from_earth_years(years: f64) -> Result<Self, DurationError> {
+        if !years.is_finite() {
+            return Err(DurationError::NonFinite);
+        }
+        Ok(Self::non_negative(years))
+    }
+
+    /// Builds a duration from an age of `planet_years` years on planet `P`, truncated to whole
+    /// seconds.
+    pub fn from_years_on<P: Planet>(planet_years: f64) -> Self {
+        Duration::from((planet_years * P::PERIOD * EARTH_YEAR_IN_SECONDS as f64) as u64)
+    }
+
+    /// Builds a duration from a number of days. As with `from_earth_years`, negative and
+    /// non-finite values are clamped to zero.
+    pub fn from_days(days: f64) -> Self {
+        Self::from_hours(days * 24.0)
+    }
+
+    /// Builds a duration from a number of hours, clamped like `from_days`.
+    pub fn from_hours(hours: f64) -> Self {
+        Self::from_minutes(hours * 60.0)
+    }
+
+    /// Builds a duration from a number of minutes, clamped like `from_days`.
+    pub fn from_minutes(minutes: f64) -> Self {
+        Self::from_earth_years(minutes * 60.0 / EARTH_YEAR_IN_SECONDS as f64)
+    }
+
+    /// Returns the duration in whole seconds, rounded to the nearest second.
+    pub fn as_seconds(&self) -> u64 {
+        (self.earth_year * EARTH_YEAR_IN_SECONDS as f64).round() as u64
+    }
+
+    /// Drops any sub-second residue, rounding to the nearest whole second.
+    pub fn rounded_to_seconds(self) -> Duration {
+        Duration::from(self.as_seconds())
+    }
+
+    pub fn as_earth_years(&self) -> f64 {
+        self.earth_year
+    }
+
+    /// Returns whether the two durations are within `epsilon` Earth years of each other.
+    pub fn approx_eq(&self, other: &Duration, epsilon: f64) -> bool {
+        years_approx_eq(self.earth_year, other.earth_year, epsilon)
+    }
+
+    /// Expresses the duration in the single most natural unit, e.g. `(18.0, "months")`.
+    ///
+    /// Durations of at least 2 years are given in years, of at least 2 months (a twelfth of a
+    /// year each) in months, of at least 1 day in days, and shorter ones in hours.
+    pub fn largest_unit(&self) -> (f64, &'static str) {
+        let years = self.earth_year;
+        let months = years * 12.0;
+        let days = years * 365.25;
+        if years >= 2.0 {
+            (years, "years")
+        } else if months >= 2.0 {
+            (months, "months")
+        } else if days >= 1.0 {
+            (days, "days")
+        } else {
+            (days * 24.0, "hours")
+        }
+    }
+
+    /// Compares the two durations, treating them as equal when they are within `epsilon` Earth
+    /// years of each other.
+    pub fn cmp_approx(&self, other: &Duration, epsilon: f64) -> Ordering {
+        if self.approx_eq(other, epsilon) {
+            return Ordering::Equal;
+        }
+        self.earth_year.total_cmp(&other.earth_year)
+    }
+
+    /// Describes the duration in whole Earth years and remaining whole days, e.g.
+    /// `"1 year, 91 days"`, using 365.25 days per year.
+    pub fn human_readable(&self) -> String {
+        fn plural(n: u64, unit: &str) -> String {
+            if n == 1 {
+                format!("{n} {unit}")
+            } else {
+                format!("{n} {unit}s")
+            }
+        }
+
+        let years = self.earth_year.floor();
+        let days = ((self.earth_year - years) * 365.25).floor() as u64;
+        let years = years as u64;
+        match (years, days) {
+            (0, days) => plural(days, "day"),
+            (years, 0) => plural(years, "year"),
+            (years, days) => format!("{}, {}", plural(years, "year"), plural(days, "day")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DurationError {
+    NonFinite,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseDurationError {
+    Empty,
+    Malformed,
+    UnknownUnit(char),
+}
+
+/// Parses compound durations such as `"1d2h3m4s"`, `"2h30m"` or `"90s"`.
+///
+/// Each of the `d`, `h`, `m` and `s` units may appear at most once, from the largest unit to the
+/// smallest, and must be preceded by a whole number.
+impl FromStr for Duration {
+    type Err = ParseDurationError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const UNITS: [(char, u64); 4] = [('d', 86_400), ('h', 3_600), ('m', 60), ('s', 1)];
+
+        if s.is_empty() {
+            return Err(ParseDurationError::Empty);
+        }
+
+        let mut seconds: u64 = 0;
+        let mut next_unit = 0;
+        let mut number: Option<u64> = None;
+        for c in s.chars() {
+            if let Some(digit) = c.to_digit(10) {
+                let n = number
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|n| n.checked_add(digit as u64))
+                    .ok_or(ParseDurationError::Malformed)?;
+                number = Some(n);
+                continue;
+            }
+
+            let position = UNITS
+                .iter()
+                .position(|(unit, _)| *unit == c)
+                .ok_or(ParseDurationError::UnknownUnit(c))?;
+            let n = number.take().ok_or(ParseDurationError::Malformed)?;
+            if position < next_unit {
+                return Err(ParseDurationError::Malformed);
+            }
+            next_unit = position + 1;
+            seconds = n
+                .checked_mul(UNITS[position].1)
+                .and_then(|unit_seconds| seconds.checked_add(unit_seconds))
+                .ok_or(ParseDurationError::Malformed)?;
+        }
+
+        if number.is_some() {
+            return Err(ParseDurationError::Malformed);
+        }
+        Ok(Duration::from(seconds))
+    }
+}
+
+/// Serializes as the number of whole seconds, matching `Duration::from(u64)`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Duration {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.as_seconds())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Duration {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        u64::deserialize(deserializer).map(Duration::from)
+    }
+}
+
+/// Durations are equal only when their Earth-year values are bit-for-bit identical, so two
+/// durations reached through different arithmetic may compare unequal even if they print the
+/// same.
+impl PartialEq for Duration {
+    fn eq(&self, other: &Self) -> bool {
+        self.earth_year.to_bits() == other.earth_year.to_bits()
+    }
+}
+
+/// Equality is bit-for-bit, which makes it reflexive: unlike with plain `f64` comparison, a `NaN`
+/// duration is equal to itself (as long as the `NaN` payloads match).
+impl Eq for Duration {}
+
+/// Hashes the bit pattern of the Earth-year value, consistently with `PartialEq`.
+impl Hash for Duration {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.earth_year.to_bits().hash(state);
+    }
+}
+
+/// Orders durations by the IEEE 754 total order of their Earth-year values, which agrees with the
+/// bit-for-bit `PartialEq`: two durations compare `Equal` exactly when they are equal.
+impl PartialOrd for Duration {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.earth_year.total_cmp(&other.earth_year))
+    }
+}
+
+impl Add for Duration {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let earth_year = self.earth_year + rhs.earth_year;
+        Self { earth_year }
+    }
+}
+
+/// A duration can't be negative, so subtracting a longer duration from a shorter one yields a
+/// zero duration.
+impl Sub for Duration {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Duration::non_negative(self.earth_year - rhs.earth_year)
+    }
+}
+
+impl Sum for Duration {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        let earth_year = iter.fold(0.0, |total, d| total + d.earth_year);
+        Self { earth_year }
+    }
+}
+
+impl<'a> Sum<&'a Duration> for Duration {
+    fn sum<I: Iterator<Item = &'a Duration>>(iter: I) -> Self {
+        let earth_year = iter.fold(0.0, |total, d| total + d.earth_year);
+        Self { earth_year }
+    }
+}
+
+/// Scaling by a negative factor yields `Duration::ZERO`.
+impl Mul<f64> for Duration {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self::Output {
+        Duration::non_negative(self.earth_year * rhs)
+    }
+}
+
+/// Dividing by zero yields a duration of `f64::INFINITY` years instead of panicking, and
+/// dividing by a negative divisor yields a zero duration.
+impl Div<f64> for Duration {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self::Output {
+        if rhs == 0.0 {
+            let earth_year = f64::INFINITY;
+            return Self { earth_year };
+        }
+        Duration::non_negative(self.earth_year / rhs)
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:.2} earth years", self.earth_year)
+    }
+}
+
+/// Accumulates durations, keeping track of their extremes and total.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DurationStats {
+    min: Option<Duration>,
+    max: Option<Duration>,
+    total: Duration,
+    count: usize,
+}
+
+impl DurationStats {
+    pub fn push(&mut self, d: Duration) {
+        self.min = Some(self.min.map_or(d, |min| if d < min { d } else { min }));
+        self.max = Some(self.max.map_or(d, |max| if d > max { d } else { max }));
+        self.total = self.total + d;
+        self.count += 1;
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.min
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.max
+    }
+
+    /// Returns the sum of the pushed durations, which is `Duration::ZERO` if there are none.
+    pub fn total(&self) -> Duration {
+        self.total
+    }
+
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        if self.count == 0 {
+            return None;
+        }
+        Some(self.total / self.count as f64)
+    }
+}
+
+/// The broad category a body falls into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlanetType {
+    Terrestrial,
+    GasGiant,
+    IceGiant,
+    /// Dwarf planets such as Pluto.
+    Dwarf,
+}
+
 pub trait Planet {
+    const NAME: &'static str;
+    const KIND: PlanetType;
+    /// Number of known natural satellites.
+    const MOON_COUNT: u32;
     const PERIOD: f64 = 1.0;
+    /// Surface gravity in m/s².
+    const GRAVITY: f64 = 9.807;
+    /// Sidereal rotation period in hours, i.e. one turn relative to the stars rather than from
+    /// noon to noon, negative for planets with retrograde rotation.
+    const DAY_LENGTH_HOURS: f64 = 23.9345;
+    /// Mean distance from the Sun in kilometres.
+    const DISTANCE_KM: f64 = 149_600_000.0;
+    /// Closest distance from the Sun in kilometres.
+    const PERIHELION_KM: f64 = 147_100_000.0;
+    /// Farthest distance from the Sun in kilometres.
+    const APHELION_KM: f64 = 152_100_000.0;
+
+    fn name() -> &'static str {
+        Self::NAME
+    }
+
+    fn moons() -> u32 {
+        Self::MOON_COUNT
+    }
+
+    fn is_gas_giant() -> bool {
+        Self::KIND == PlanetType::GasGiant
+    }
 
     fn years_during(d: &Duration) -> f64 {
         d.earth_year / Self::PERIOD
     }
+
+    /// Returns `years_during(d)` rounded to `decimals` decimal places.
+    fn years_during_rounded(d: &Duration, decimals: u32) -> f64 {
+        let factor = 10f64.powi(decimals as i32);
+        (Self::years_during(d) * factor).round() / factor
+    }
+
+    /// Returns the number of whole years in `d`, e.g. someone's age.
+    fn whole_years(d: &Duration) -> u64 {
+        Self::years_during(d).floor().max(0.0) as u64
+    }
+
+    fn years_during_f32(d: &Duration32) -> f32 {
+        d.earth_year / Self::PERIOD as f32
+    }
+
+    /// Returns the apparent weight, in kilograms, of something weighing `earth_weight_kg` on Earth.
+    fn weight_on(earth_weight_kg: f64) -> f64 {
+        earth_weight_kg * Self::GRAVITY / Earth::GRAVITY
+    }
+
+    /// Returns how many sidereal days fit into one local year.
+    fn days_in_year() -> f64 {
+        (Self::PERIOD * EARTH_YEAR_IN_SECONDS as f64) / (Self::DAY_LENGTH_HOURS.abs() * 3600.0)
+    }
+
+    /// Returns how many minutes sunlight takes to reach the planet.
+    fn light_minutes_from_sun() -> f64 {
+        Self::DISTANCE_KM / (299_792.458 * 60.0)
+    }
+
+    /// Returns the average of the perihelion and aphelion distances in kilometres.
+    fn mean_distance_km() -> f64 {
+        (Self::PERIHELION_KM + Self::APHELION_KM) / 2.0
+    }
+
+    /// Returns the orbital period in Earth days.
+    fn period_in_days() -> f64 {
+        Self::PERIOD * 365.25
+    }
+
+    /// Returns the length of one local year.
+    fn orbital_duration() -> Duration {
+        Duration::from((Self::PERIOD * EARTH_YEAR_IN_SECONDS as f64) as u64)
+    }
 }
 
 pub struct Mercury;
@@ -31,26 +490,407 @@ pub struct Jupiter;
 pub struct Saturn;
 pub struct Uranus;
 pub struct Neptune;
+pub struct Pluto;
+/// Earth's Moon. Its period is the time it takes to orbit Earth rather than the Sun, and its
+/// distances from the Sun are taken to be Earth's.
+pub struct Moon;
 
 impl Planet for Mercury {
+    const NAME: &'static str = "Mercury";
+    const KIND: PlanetType = PlanetType::Terrestrial;
+    const MOON_COUNT: u32 = 0;
     const PERIOD: f64 = 0.2408467;
+    const GRAVITY: f64 = 3.7;
+    const DAY_LENGTH_HOURS: f64 = 1407.6;
+    const DISTANCE_KM: f64 = 57_900_000.0;
+    const PERIHELION_KM: f64 = 46_000_000.0;
+    const APHELION_KM: f64 = 69_800_000.0;
 }
 impl Planet for Venus {
+    const NAME: &'static str = "Venus";
+    const KIND: PlanetType = PlanetType::Terrestrial;
+    const MOON_COUNT: u32 = 0;
     const PERIOD: f64 = 0.61519726;
+    const GRAVITY: f64 = 8.87;
+    const DAY_LENGTH_HOURS: f64 = -5832.5;
+    const DISTANCE_KM: f64 = 108_200_000.0;
+    const PERIHELION_KM: f64 = 107_500_000.0;
+    const APHELION_KM: f64 = 108_900_000.0;
+}
+impl Planet for Earth {
+    const NAME: &'static str = "Earth";
+    const KIND: PlanetType = PlanetType::Terrestrial;
+    const MOON_COUNT: u32 = 1;
 }
-impl Planet for Earth {}
 impl Planet for Mars {
+    const NAME: &'static str = "Mars";
+    const KIND: PlanetType = PlanetType::Terrestrial;
+    const MOON_COUNT: u32 = 2;
     const PERIOD: f64 = 1.8808158;
+    const GRAVITY: f64 = 3.721;
+    const DAY_LENGTH_HOURS: f64 = 24.6229;
+    const DISTANCE_KM: f64 = 227_900_000.0;
+    const PERIHELION_KM: f64 = 206_700_000.0;
+    const APHELION_KM: f64 = 249_300_000.0;
 }
 impl Planet for Jupiter {
+    const NAME: &'static str = "Jupiter";
+    const KIND: PlanetType = PlanetType::GasGiant;
+    const MOON_COUNT: u32 = 95;
     const PERIOD: f64 = 11.862615;
+    const GRAVITY: f64 = 24.79;
+    const DAY_LENGTH_HOURS: f64 = 9.925;
+    const DISTANCE_KM: f64 = 778_500_000.0;
+    const PERIHELION_KM: f64 = 740_600_000.0;
+    const APHELION_KM: f64 = 816_400_000.0;
 }
 impl Planet for Saturn {
+    const NAME: &'static str = "Saturn";
+    const KIND: PlanetType = PlanetType::GasGiant;
+    const MOON_COUNT: u32 = 146;
     const PERIOD: f64 = 29.447498;
+    const GRAVITY: f64 = 10.44;
+    const DAY_LENGTH_HOURS: f64 = 10.656;
+    const DISTANCE_KM: f64 = 1_432_000_000.0;
+    const PERIHELION_KM: f64 = 1_357_600_000.0;
+    const APHELION_KM: f64 = 1_506_500_000.0;
 }
 impl Planet for Uranus {
+    const NAME: &'static str = "Uranus";
+    const KIND: PlanetType = PlanetType::IceGiant;
+    const MOON_COUNT: u32 = 28;
     const PERIOD: f64 = 84.016846;
+    const GRAVITY: f64 = 8.87;
+    const DAY_LENGTH_HOURS: f64 = -17.24;
+    const DISTANCE_KM: f64 = 2_867_000_000.0;
+    const PERIHELION_KM: f64 = 2_732_700_000.0;
+    const APHELION_KM: f64 = 3_001_400_000.0;
 }
 impl Planet for Neptune {
+    const NAME: &'static str = "Neptune";
+    const KIND: PlanetType = PlanetType::IceGiant;
+    const MOON_COUNT: u32 = 16;
     const PERIOD: f64 = 164.79132;
+    const GRAVITY: f64 = 11.15;
+    const DAY_LENGTH_HOURS: f64 = 16.11;
+    const DISTANCE_KM: f64 = 4_515_000_000.0;
+    const PERIHELION_KM: f64 = 4_471_100_000.0;
+    const APHELION_KM: f64 = 4_558_900_000.0;
+}
+impl Planet for Pluto {
+    const NAME: &'static str = "Pluto";
+    const KIND: PlanetType = PlanetType::Dwarf;
+    const MOON_COUNT: u32 = 5;
+    const PERIOD: f64 = 247.94;
+    const GRAVITY: f64 = 0.62;
+    const DAY_LENGTH_HOURS: f64 = -153.2928;
+    const DISTANCE_KM: f64 = 5_906_400_000.0;
+    const PERIHELION_KM: f64 = 4_434_000_000.0;
+    const APHELION_KM: f64 = 7_304_300_000.0;
+}
+impl Planet for Moon {
+    const NAME: &'static str = "Moon";
+    const KIND: PlanetType = PlanetType::Terrestrial;
+    const MOON_COUNT: u32 = 0;
+    const PERIOD: f64 = 0.0748;
+    const GRAVITY: f64 = 1.62;
+    const DAY_LENGTH_HOURS: f64 = 655.7;
+}
+
+/// The orbital period, in Earth years, of each planet ordered from Mercury outward.
+pub const PLANET_PERIODS: [(&str, f64); 8] = [
+    (Mercury::NAME, Mercury::PERIOD),
+    (Venus::NAME, Venus::PERIOD),
+    (Earth::NAME, Earth::PERIOD),
+    (Mars::NAME, Mars::PERIOD),
+    (Jupiter::NAME, Jupiter::PERIOD),
+    (Saturn::NAME, Saturn::PERIOD),
+    (Uranus::NAME, Uranus::PERIOD),
+    (Neptune::NAME, Neptune::PERIOD),
+];
+
+/// Returns the orbital period of `P` in Earth years.
+pub fn orbital_period<P: Planet>() -> f64 {
+    P::PERIOD
+}
+
+/// Returns whether two year counts differ by at most `epsilon`. The epsilon is absolute, in the
+/// same unit as the year counts, rather than relative to their magnitude.
+pub fn years_approx_eq(a: f64, b: f64, epsilon: f64) -> bool {
+    (a - b).abs() <= epsilon
+}
+
+/// Returns the age, in years of planet `P`, of someone born at `birth_unix_secs` as of
+/// `now_unix_secs`. A birth in the future yields an age of zero.
+pub fn age_on<P: Planet>(birth_unix_secs: u64, now_unix_secs: u64) -> f64 {
+    P::years_during(&Duration::between(birth_unix_secs, now_unix_secs))
+}
+
+/// Returns how many seconds are left until the next whole-year birthday on planet `P` for
+/// someone who has lived for `lived`. Exactly on a birthday, the next one is a full year away.
+pub fn seconds_until_next_birthday<P: Planet>(lived: &Duration) -> u64 {
+    let next_birthday = P::years_during(lived).floor() + 1.0;
+    let remaining = next_birthday * P::PERIOD - lived.earth_year;
+    (remaining * EARTH_YEAR_IN_SECONDS as f64).round() as u64
+}
+
+/// Returns how far through the current year on planet `P` someone who has lived for `lived` is, in
+/// `[0.0, 1.0)`. Exactly on a birthday this is 0.0.
+pub fn fraction_of_current_year<P: Planet>(lived: &Duration) -> f64 {
+    P::years_during(lived).fract()
+}
+
+/// Converts an age of `years_on_from` years on planet `A` into years on planet `B`.
+pub fn convert_years<A: Planet, B: Planet>(years_on_from: f64) -> f64 {
+    years_on_from * A::PERIOD / B::PERIOD
+}
+
+/// Returns how many years of planet `B` fit into one year of planet `A`.
+pub fn year_ratio<A: Planet, B: Planet>() -> f64 {
+    A::PERIOD / B::PERIOD
+}
+
+/// Returns the total number of years on planet `P` across all of `durations`, which is 0.0 for no
+/// durations.
+pub fn total_years<P: Planet>(durations: &[Duration]) -> f64 {
+    durations
+        .iter()
+        .fold(0.0, |total, d| total + P::years_during(d))
+}
+
+/// Returns how many more years `d` amounts to on planet `A` than on planet `B`, which is negative
+/// if `A` has the longer year.
+pub fn age_difference<A: Planet, B: Planet>(d: &Duration) -> f64 {
+    A::years_during(d) - B::years_during(d)
+}
+
+/// A planet whose orbital period, in Earth years, is only known at runtime.
+pub struct CustomPlanet {
+    pub period: f64,
+}
+
+impl CustomPlanet {
+    pub fn years_during(&self, d: &Duration) -> f64 {
+        d.earth_year / self.period
+    }
+}
+
+/// The eight planets of the solar system, for when the planet is only known at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PlanetKind {
+    Mercury,
+    Venus,
+    Earth,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParsePlanetError;
+
+// Evaluates `$body` with `$planet` bound to the planet type matching `$kind`.
+macro_rules! with_planet {
+    ($kind:expr, $planet:ident => $body:expr) => {
+        match $kind {
+            PlanetKind::Mercury => {
+                type $planet = Mercury;
+                $body
+            }
+            PlanetKind::Venus => {
+                type $planet = Venus;
+                $body
+            }
+            PlanetKind::Earth => {
+                type $planet = Earth;
+                $body
+            }
+            PlanetKind::Mars => {
+                type $planet = Mars;
+                $body
+            }
+            PlanetKind::Jupiter => {
+                type $planet = Jupiter;
+                $body
+            }
+            PlanetKind::Saturn => {
+                type $planet = Saturn;
+                $body
+            }
+            PlanetKind::Uranus => {
+                type $planet = Uranus;
+                $body
+            }
+            PlanetKind::Neptune => {
+                type $planet = Neptune;
+                $body
+            }
+        }
+    };
+}
+
+impl PlanetKind {
+    /// All planets, ordered from Mercury outward.
+    pub const ALL: [PlanetKind; 8] = [
+        PlanetKind::Mercury,
+        PlanetKind::Venus,
+        PlanetKind::Earth,
+        PlanetKind::Mars,
+        PlanetKind::Jupiter,
+        PlanetKind::Saturn,
+        PlanetKind::Uranus,
+        PlanetKind::Neptune,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        with_planet!(self, P => P::NAME)
+    }
+
+    pub fn period(&self) -> f64 {
+        with_planet!(self, P => P::PERIOD)
+    }
+
+    pub fn years_during(&self, d: &Duration) -> f64 {
+        with_planet!(self, P => P::years_during(d))
+    }
+}
+
+/// Parses a planet name, ignoring case.
+impl FromStr for PlanetKind {
+    type Err = ParsePlanetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mercury" => Ok(PlanetKind::Mercury),
+            "venus" => Ok(PlanetKind::Venus),
+            "earth" => Ok(PlanetKind::Earth),
+            "mars" => Ok(PlanetKind::Mars),
+            "jupiter" => Ok(PlanetKind::Jupiter),
+            "saturn" => Ok(PlanetKind::Saturn),
+            "uranus" => Ok(PlanetKind::Uranus),
+            "neptune" => Ok(PlanetKind::Neptune),
+            _ => Err(ParsePlanetError),
+        }
+    }
+}
+
+/// Returns the name of every planet paired with the number of its years in `d`, ordered from
+/// Mercury outward.
+pub fn all_planet_years(d: &Duration) -> Vec<(&'static str, f64)> {
+    PlanetAges::new(d).collect()
+}
+
+/// Lazily yields the name of every planet paired with the number of its years in a duration,
+/// ordered from Mercury outward.
+#[derive(Clone, Debug)]
+pub struct PlanetAges {
+    duration: Duration,
+    next: usize,
+}
+
+impl PlanetAges {
+    pub fn new(d: &Duration) -> Self {
+        PlanetAges {
+            duration: *d,
+            next: 0,
+        }
+    }
+}
+
+impl Iterator for PlanetAges {
+    type Item = (&'static str, f64);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let planet = PlanetKind::ALL.get(self.next)?;
+        self.next += 1;
+        Some((planet.name(), planet.years_during(&self.duration)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = PlanetKind::ALL.len() - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+/// Estimates how many leap years the whole Earth years in `d` contain, following the
+/// Gregorian rule of 97 leap years every 400 years.
+///
+/// This is an approximation: the years are counted as if they started at year 1 of the Gregorian
+/// calendar, so the same duration starting at a different date may span one leap year more or
+/// less.
+pub fn leap_years_in(d: &Duration) -> u64 {
+    let years = d.earth_year.floor() as u64;
+    years / 4 - years / 100 + years / 400
+}
+
+/// Returns the planets, ordered from Mercury outward, on which `d` amounts to at least one
+/// year.
+pub fn planets_with_birthday(d: &Duration) -> Vec<&'static str> {
+    PlanetAges::new(d)
+        .filter(|(_, years)| *years >= 1.0)
+        .map(|(name, _)| name)
+        .collect()
+}
+
+/// Returns the planet on which `d` amounts to the fewest years, i.e. the one with the longest
+/// orbital period, along with that year count.
+pub fn youngest_on(d: &Duration) -> (&'static str, f64) {
+    all_planet_years(d)
+        .into_iter()
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .expect("there is at least one planet")
+}
+
+/// Returns the planet on which `d` amounts to the most years, i.e. the one with the shortest
+/// orbital period, along with that year count.
+pub fn oldest_on(d: &Duration) -> (&'static str, f64) {
+    all_planet_years(d)
+        .into_iter()
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .expect("there is at least one planet")
+}
+
+/// Returns every planet's name and orbital period, sorted by ascending period.
+pub fn planets_by_period() -> Vec<(&'static str, f64)> {
+    let mut planets = PLANET_PERIODS.to_vec();
+    planets.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+    planets
+}
+
+/// Returns the planet whose orbital period is nearest `period` Earth years, preferring the inner
+/// planet on a tie.
+pub fn closest_planet(period: f64) -> &'static str {
+    PLANET_PERIODS
+        .iter()
+        .min_by(|(_, a), (_, b)| (a - period).abs().total_cmp(&(b - period).abs()))
+        .map(|(name, _)| *name)
+        .expect("there is at least one planet")
+}
+
+// Describes one planet as a JSON object.
+#[cfg(feature = "json")]
+fn planet_json<P: Planet>() -> serde_json::Value {
+    serde_json::json!({
+        "name": P::NAME,
+        "period": P::PERIOD,
+        "gravity": P::GRAVITY,
+        "day_length_hours": P::DAY_LENGTH_HOURS,
+        "distance_km": P::DISTANCE_KM,
+        "perihelion_km": P::PERIHELION_KM,
+        "aphelion_km": P::APHELION_KM,
+        "kind": format!("{:?}", P::KIND),
+        "moon_count": P::MOON_COUNT,
+    })
+}
+
+/// Returns a JSON array describing each planet, ordered from Mercury outward.
+#[cfg(feature = "json")]
+pub fn planet_table_json() -> String {
+    let planets = PlanetKind::ALL
+        .iter()
+        .map(|planet| with_planet!(planet, P => planet_json::<P>()))
+        .collect();
+    serde_json::Value::Array(planets).to_string()
 }
@@ -55,3 +55,703 @@ fn neptune_age() {
     let duration = Duration::from(1_821_023_456);
     assert_in_delta(0.35, Neptune::years_during(&duration));
 }
+
+#[test]
+fn pluto_age() {
+    let duration = Duration::from(10_000_000_000);
+    assert_in_delta(1.28, Pluto::years_during(&duration));
+}
+
+#[test]
+fn moon_age() {
+    let duration = Duration::from(1_000_000_000);
+    assert_in_delta(423.64, Moon::years_during(&duration));
+}
+
+#[test]
+fn custom_planet_age() {
+    let planet = CustomPlanet { period: 5.2 };
+    let duration = Duration::from(1_000_000_000);
+    assert_in_delta(6.09, planet.years_during(&duration));
+}
+
+#[test]
+fn duration_display() {
+    assert_eq!(Duration::from(31557600).to_string(), "1.00 earth years");
+    assert_eq!(
+        Duration::from(1_000_000_000).to_string(),
+        "31.69 earth years"
+    );
+}
+
+#[test]
+fn duration_from_days_hours_and_minutes() {
+    let year = Earth::years_during(&Duration::from(31557600));
+    assert_in_delta(year, Earth::years_during(&Duration::from_days(365.25)));
+    assert_in_delta(year, Earth::years_during(&Duration::from_hours(8766.0)));
+    assert_in_delta(
+        year,
+        Earth::years_during(&Duration::from_minutes(525_960.0)),
+    );
+    assert_in_delta(0.5, Earth::years_during(&Duration::from_days(182.625)));
+    assert_in_delta(0.0, Earth::years_during(&Duration::from_days(0.0)));
+}
+
+#[test]
+fn durations_can_be_added() {
+    let total = Duration::from(31557600) + Duration::from_days(365.25);
+    assert_in_delta(2.0, Earth::years_during(&total));
+}
+
+#[test]
+fn durations_can_be_subtracted() {
+    let difference = Duration::from(3 * 31557600) - Duration::from(31557600);
+    assert_in_delta(2.0, Earth::years_during(&difference));
+}
+
+#[test]
+fn subtracting_a_longer_duration_clamps_to_zero() {
+    let difference = Duration::from(31557600) - Duration::from(2 * 31557600);
+    assert_eq!(0.0, Earth::years_during(&difference));
+}
+
+#[test]
+fn durations_can_be_compared() {
+    let short = Duration::from(1_000);
+    let medium = Duration::from(1_000_000);
+    let long = Duration::from(1_000_000_000);
+    assert!(short < medium);
+    assert!(medium < long);
+    assert!(long > short);
+    assert!(medium == Duration::from(1_000_000));
+    assert!(short != long);
+}
+
+#[test]
+fn duration_round_trips_through_seconds() {
+    assert_eq!(1_000_000, Duration::from(1_000_000).as_seconds());
+    assert_eq!(31557600, Duration::from_days(365.25).as_seconds());
+    assert_in_delta(31.69, Duration::from(1_000_000_000).as_earth_years());
+}
+
+#[test]
+fn parse_compound_duration() {
+    let duration: Duration = "1d2h3m4s".parse().unwrap();
+    assert_eq!(86_400 + 2 * 3_600 + 3 * 60 + 4, duration.as_seconds());
+}
+
+#[test]
+fn parse_single_unit_duration() {
+    assert_eq!(2_700, "45m".parse::<Duration>().unwrap().as_seconds());
+    assert_eq!(90, "90s".parse::<Duration>().unwrap().as_seconds());
+}
+
+#[test]
+fn parse_invalid_durations() {
+    assert_eq!(
+        "10x".parse::<Duration>(),
+        Err(ParseDurationError::UnknownUnit('x'))
+    );
+    assert_eq!("".parse::<Duration>(), Err(ParseDurationError::Empty));
+    assert_eq!("10".parse::<Duration>(), Err(ParseDurationError::Malformed));
+    assert_eq!("h".parse::<Duration>(), Err(ParseDurationError::Malformed));
+    assert_eq!(
+        "3m2h".parse::<Duration>(),
+        Err(ParseDurationError::Malformed)
+    );
+}
+
+#[test]
+fn planet_kind_from_name() {
+    assert_eq!("MARS".parse(), Ok(PlanetKind::Mars));
+    assert_eq!("jupiter".parse(), Ok(PlanetKind::Jupiter));
+    assert_eq!("NePtUnE".parse(), Ok(PlanetKind::Neptune));
+    assert_eq!("Krypton".parse::<PlanetKind>(), Err(ParsePlanetError));
+}
+
+#[test]
+fn planet_kind_matches_planet_types() {
+    let duration = Duration::from(901_876_382);
+    let jupiter: PlanetKind = "jupiter".parse().unwrap();
+    assert_eq!(Jupiter::PERIOD, jupiter.period());
+    assert_eq!(
+        Jupiter::years_during(&duration),
+        jupiter.years_during(&duration)
+    );
+}
+
+#[test]
+fn years_on_all_planets() {
+    let duration = Duration::from(1_000_000_000);
+    let years = all_planet_years(&duration);
+    let names: Vec<_> = years.iter().map(|(name, _)| *name).collect();
+    assert_eq!(
+        names,
+        ["Mercury", "Venus", "Earth", "Mars", "Jupiter", "Saturn", "Uranus", "Neptune"]
+    );
+    assert_eq!(years[2].1, duration.as_earth_years());
+    assert_eq!(years[3].1, Mars::years_during(&duration));
+}
+
+#[test]
+fn weight_on_other_planets() {
+    assert_in_delta(100.0, Earth::weight_on(100.0));
+    assert_in_delta(37.94, Mars::weight_on(100.0));
+    assert_in_delta(252.78, Jupiter::weight_on(100.0));
+}
+
+#[test]
+fn days_in_a_local_year() {
+    assert_in_delta(366.25, Earth::days_in_year());
+    assert_in_delta(669.59, Mars::days_in_year());
+}
+
+#[test]
+fn retrograde_rotation_still_counts_positive_days() {
+    assert!(Venus::days_in_year() > 0.0);
+    assert!(Uranus::days_in_year() > 0.0);
+}
+
+#[test]
+fn age_from_unix_timestamps() {
+    let birth = 946_684_800;
+    assert_in_delta(1.0, age_on::<Earth>(birth, birth + 31557600));
+    assert_in_delta(4.15, age_on::<Mercury>(birth, birth + 31557600));
+}
+
+#[test]
+fn age_is_zero_when_now_precedes_birth() {
+    assert_eq!(0.0, age_on::<Earth>(946_684_800, 0));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn duration_serde_round_trip() {
+    let json = serde_json::to_string(&Duration::from(31557600)).unwrap();
+    assert_eq!(json, "31557600");
+    let duration: Duration = serde_json::from_str(&json).unwrap();
+    assert_eq!(duration, Duration::from(31557600));
+}
+
+#[test]
+fn single_precision_ages() {
+    let seconds = 2_134_835_688;
+    let expected = Mercury::years_during(&Duration::from(seconds));
+    let actual = Mercury::years_during_f32(&Duration32::from(seconds));
+    assert!((expected - actual as f64).abs() < 0.001 * expected);
+    assert!((Neptune::years_during_f32(&Duration32::from(1_821_023_456)) - 0.35).abs() < 0.01);
+}
+
+#[test]
+fn rounded_ages() {
+    let duration = Duration::from(2_134_835_688);
+    assert_eq!(280.88, Mercury::years_during_rounded(&duration, 2));
+    assert_eq!(281.0, Mercury::years_during_rounded(&duration, 0));
+}
+
+#[test]
+fn duration_from_std_duration() {
+    let duration = Duration::from(std::time::Duration::from_secs(31557600));
+    assert_eq!(1.0, duration.as_earth_years());
+    let duration = Duration::from(std::time::Duration::from_millis(1_500));
+    assert_eq!(Duration::from_minutes(0.025), duration);
+}
+
+#[test]
+fn planet_period_table() {
+    assert_eq!(31557600, EARTH_YEAR_IN_SECONDS);
+    assert_eq!(Mars::PERIOD, orbital_period::<Mars>());
+    let expected = [
+        ("Mercury", Mercury::PERIOD),
+        ("Venus", Venus::PERIOD),
+        ("Earth", Earth::PERIOD),
+        ("Mars", Mars::PERIOD),
+        ("Jupiter", Jupiter::PERIOD),
+        ("Saturn", Saturn::PERIOD),
+        ("Uranus", Uranus::PERIOD),
+        ("Neptune", Neptune::PERIOD),
+    ];
+    assert_eq!(expected, PLANET_PERIODS);
+    for (name, period) in PLANET_PERIODS {
+        assert_eq!(period, name.parse::<PlanetKind>().unwrap().period());
+    }
+}
+
+#[test]
+fn youngest_and_oldest_planets() {
+    for seconds in [1, 1_000_000, 2_000_000_000] {
+        let duration = Duration::from(seconds);
+        assert_eq!(
+            ("Neptune", Neptune::years_during(&duration)),
+            youngest_on(&duration)
+        );
+        assert_eq!(
+            ("Mercury", Mercury::years_during(&duration)),
+            oldest_on(&duration)
+        );
+    }
+}
+
+#[test]
+fn durations_can_be_scaled() {
+    assert_eq!(2.5, (Duration::from(31557600) * 2.5).as_earth_years());
+    assert_eq!(2.0, (Duration::from(31557600) / 0.5).as_earth_years());
+}
+
+#[test]
+fn dividing_a_duration_by_zero_is_infinite() {
+    assert_eq!(
+        f64::INFINITY,
+        (Duration::from(31557600) / 0.0).as_earth_years()
+    );
+}
+
+#[test]
+fn human_readable_durations() {
+    assert_eq!("1 year", Duration::from(31557600).human_readable());
+    assert_eq!("2 years", Duration::from(2 * 31557600).human_readable());
+    assert_eq!(
+        "1 year, 91 days",
+        Duration::from_days(365.25 + 91.0).human_readable()
+    );
+    assert_eq!("1 day", Duration::from_days(1.0).human_readable());
+    assert_eq!("0 days", Duration::from(0).human_readable());
+}
+
+#[test]
+fn planet_names() {
+    assert_eq!("Saturn", Saturn::name());
+    assert_eq!("Moon", Moon::name());
+    assert_eq!("Earth", PlanetKind::Earth.name());
+}
+
+#[test]
+fn zero_duration() {
+    assert_eq!(0.0, Duration::default().as_earth_years());
+    assert_eq!(Duration::ZERO, Duration::from(0));
+    assert_eq!(
+        Duration::from(1_000_000_000),
+        Duration::from(1_000_000_000) + Duration::ZERO
+    );
+}
+
+#[test]
+fn durations_can_be_summed() {
+    let durations = vec![
+        Duration::from(1_000),
+        Duration::from(1_000_000),
+        Duration::from(1_000_000_000),
+    ];
+    let expected: f64 = durations.iter().map(Duration::as_earth_years).sum();
+    assert_eq!(
+        expected,
+        durations.iter().sum::<Duration>().as_earth_years()
+    );
+    assert_eq!(
+        expected,
+        durations.into_iter().sum::<Duration>().as_earth_years()
+    );
+    assert_eq!(Duration::ZERO, Vec::<Duration>::new().into_iter().sum());
+}
+
+#[test]
+fn convert_years_between_planets() {
+    assert_in_delta(7.81, convert_years::<Mars, Mercury>(1.0));
+    assert_in_delta(1.0, convert_years::<Mercury, Mars>(7.81));
+    assert_in_delta(Mars::PERIOD, convert_years::<Mars, Earth>(1.0));
+}
+
+#[test]
+fn planets_sorted_by_period() {
+    let planets = planets_by_period();
+    assert_eq!(8, planets.len());
+    assert_eq!("Mercury", planets[0].0);
+    assert_eq!("Neptune", planets[7].0);
+    assert!(planets.windows(2).all(|pair| pair[0].1 < pair[1].1));
+}
+
+#[test]
+fn duration_from_earth_years() {
+    assert_eq!(
+        2 * EARTH_YEAR_IN_SECONDS,
+        Duration::from_earth_years(2.0).as_seconds()
+    );
+    assert_eq!(Duration::ZERO, Duration::from_earth_years(-3.0));
+}
+
+#[test]
+fn light_travel_time_from_the_sun() {
+    assert_in_delta(8.32, Earth::light_minutes_from_sun());
+    assert_in_delta(251.01, Neptune::light_minutes_from_sun());
+    assert!(Neptune::light_minutes_from_sun() > 30.0 * Earth::light_minutes_from_sun());
+}
+
+#[test]
+fn seconds_until_next_earth_birthday() {
+    let lived = Duration::from(EARTH_YEAR_IN_SECONDS / 2);
+    assert_eq!(
+        EARTH_YEAR_IN_SECONDS / 2,
+        seconds_until_next_birthday::<Earth>(&lived)
+    );
+    let lived = Duration::from(3 * EARTH_YEAR_IN_SECONDS);
+    assert_eq!(
+        EARTH_YEAR_IN_SECONDS,
+        seconds_until_next_birthday::<Earth>(&lived)
+    );
+}
+
+#[test]
+fn seconds_until_next_mars_birthday() {
+    assert_eq!(
+        59_354_033,
+        seconds_until_next_birthday::<Mars>(&Duration::ZERO)
+    );
+    let lived = Duration::from(EARTH_YEAR_IN_SECONDS);
+    assert_eq!(27_796_433, seconds_until_next_birthday::<Mars>(&lived));
+}
+
+#[test]
+fn duration_between_timestamps() {
+    assert_eq!(500, Duration::between(1_000, 1_500).as_seconds());
+    assert_eq!(Duration::ZERO, Duration::between(1_500, 1_000));
+}
+
+#[test]
+fn durations_are_copied_by_value() {
+    let mut original = Duration::from(1_000_000);
+    let copy = original;
+    original = original + Duration::from(1_000);
+    assert_eq!(Duration::from(1_000_000), copy);
+    assert_ne!(original, copy);
+    assert_eq!(1_001_000, original.as_seconds());
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn planet_table_as_json() {
+    let json = planet_table_json();
+    for (name, _) in PLANET_PERIODS {
+        assert!(json.contains(name));
+    }
+    let records: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+    assert_eq!(8, records.len());
+    assert_eq!("Mars", records[3]["name"]);
+    assert_eq!(Mars::PERIOD, records[3]["period"]);
+    assert_eq!(Mars::MOON_COUNT, records[3]["moon_count"]);
+    assert_eq!(Mars::APHELION_KM, records[3]["aphelion_km"]);
+    assert_eq!("GasGiant", records[4]["kind"]);
+}
+
+#[test]
+fn ratio_of_planet_years() {
+    assert_in_delta(11.86, year_ratio::<Jupiter, Earth>());
+    assert_in_delta(0.53, year_ratio::<Earth, Mars>());
+}
+
+#[test]
+fn approximately_equal_durations() {
+    let a = Duration::from(std::time::Duration::from_secs(1_000_000_000));
+    let b = Duration::from(std::time::Duration::from_micros(1_000_000_000_000_005));
+    assert_ne!(a, b);
+    assert!(a.approx_eq(&b, 1e-9));
+    assert!(!a.approx_eq(&b, 1e-15));
+    assert!(years_approx_eq(
+        Mars::years_during(&a),
+        Mars::years_during(&b),
+        1e-9
+    ));
+}
+
+#[test]
+fn durations_can_be_hash_map_keys() {
+    let mut ages = std::collections::HashMap::new();
+    ages.insert(Duration::from(1_000_000_000), "first");
+    ages.insert(Duration::from(1_000_000_000), "second");
+    ages.insert(Duration::from(2_000_000_000), "other");
+    assert_eq!(2, ages.len());
+    assert_eq!(Some(&"second"), ages.get(&Duration::from(1_000_000_000)));
+}
+
+#[test]
+fn rounding_to_whole_seconds() {
+    let duration = Duration::from_earth_years(1.0000001);
+    let rounded = duration.rounded_to_seconds();
+    assert_eq!(31_557_603, rounded.as_seconds());
+    assert_eq!(Duration::from(31_557_603), rounded);
+    let seconds = rounded.as_earth_years() * EARTH_YEAR_IN_SECONDS as f64;
+    assert_in_delta(seconds.round(), seconds);
+}
+
+#[test]
+fn orbital_periods_in_earth_days() {
+    assert_eq!(365.25, Earth::period_in_days());
+    assert!((Jupiter::period_in_days() - 4_333.0).abs() < 1.0);
+}
+
+#[test]
+fn closest_planet_by_period() {
+    assert_eq!("Earth", closest_planet(1.1));
+    assert_eq!("Saturn", closest_planet(30.0));
+}
+
+#[test]
+fn closest_planet_prefers_the_inner_planet_on_a_tie() {
+    let midpoint = (Earth::PERIOD + Mars::PERIOD) / 2.0;
+    assert_eq!("Earth", closest_planet(midpoint));
+}
+
+#[test]
+fn duration_stats_aggregate_pushed_durations() {
+    let mut stats = DurationStats::default();
+    stats.push(Duration::from(2_000_000_000));
+    stats.push(Duration::from(1_000_000_000));
+    stats.push(Duration::from(3_000_000_000));
+    assert_eq!(3, stats.count());
+    assert_eq!(Some(Duration::from(1_000_000_000)), stats.min());
+    assert_eq!(Some(Duration::from(3_000_000_000)), stats.max());
+    assert_eq!(6_000_000_000, stats.total().as_seconds());
+    assert_eq!(Some(2_000_000_000), stats.mean().map(|d| d.as_seconds()));
+}
+
+#[test]
+fn empty_duration_stats() {
+    let stats = DurationStats::default();
+    assert_eq!(0, stats.count());
+    assert_eq!(None, stats.min());
+    assert_eq!(None, stats.max());
+    assert_eq!(Duration::ZERO, stats.total());
+    assert_eq!(None, stats.mean());
+}
+
+#[test]
+fn fraction_of_the_current_earth_year() {
+    let lived = Duration::from(EARTH_YEAR_IN_SECONDS * 5 / 4);
+    assert_in_delta(0.25, fraction_of_current_year::<Earth>(&lived));
+    let birthday = Duration::from(EARTH_YEAR_IN_SECONDS);
+    assert_eq!(0.0, fraction_of_current_year::<Earth>(&birthday));
+}
+
+#[test]
+fn fraction_of_the_current_martian_year() {
+    let lived = Duration::from_earth_years(2.3 * Mars::PERIOD);
+    assert_in_delta(0.3, fraction_of_current_year::<Mars>(&lived));
+    let birthday = Duration::from_earth_years(2.0 * Mars::PERIOD);
+    assert_eq!(0.0, fraction_of_current_year::<Mars>(&birthday));
+}
+
+#[test]
+fn durations_convert_back_into_seconds() {
+    assert_eq!(12345, u64::from(Duration::from(12345)));
+    let secs: u64 = Duration::from(1_000_000_000).into();
+    assert_eq!(1_000_000_000, secs);
+}
+
+#[test]
+fn orbital_periods_as_durations() {
+    assert_eq!(
+        EARTH_YEAR_IN_SECONDS,
+        Earth::orbital_duration().as_seconds()
+    );
+    assert_in_delta(Mars::PERIOD, Mars::orbital_duration().as_earth_years());
+}
+
+#[test]
+fn non_finite_earth_years_are_rejected() {
+    assert_eq!(
+        Err(DurationError::NonFinite),
+        Duration::try_from_earth_years(f64::NAN)
+    );
+    assert_eq!(
+        Err(DurationError::NonFinite),
+        Duration::try_from_earth_years(f64::INFINITY)
+    );
+    assert_eq!(
+        Ok(Duration::from_earth_years(1.5)),
+        Duration::try_from_earth_years(1.5)
+    );
+    assert_eq!(Duration::ZERO, Duration::from_earth_years(f64::NAN));
+    assert_eq!(Duration::ZERO, Duration::from_earth_years(f64::INFINITY));
+}
+
+#[test]
+fn planet_ages_are_yielded_lazily() {
+    let duration = Duration::from(1_000_000_000);
+    let ages = PlanetAges::new(&duration).collect::<Vec<_>>();
+    assert_eq!(8, ages.len());
+    assert_eq!(("Mercury", Mercury::years_during(&duration)), ages[0]);
+    assert_eq!(("Earth", Earth::years_during(&duration)), ages[2]);
+    assert_eq!(("Neptune", Neptune::years_during(&duration)), ages[7]);
+
+    let first_two = PlanetAges::new(&duration).take(2).collect::<Vec<_>>();
+    assert_eq!(ages[..2], first_two[..]);
+}
+
+#[test]
+fn age_difference_between_planets() {
+    let duration = Duration::from(2_134_835_688);
+    let difference = age_difference::<Mercury, Earth>(&duration);
+    assert!(difference > 0.0);
+    assert_in_delta(280.88 - 67.65, difference);
+    assert_in_delta(-difference, age_difference::<Earth, Mercury>(&duration));
+}
+
+#[test]
+fn short_durations_are_given_in_hours() {
+    let (hours, unit) = Duration::from_hours(5.0).largest_unit();
+    assert_eq!("hours", unit);
+    assert_in_delta(5.0, hours);
+}
+
+#[test]
+fn multi_month_durations_are_given_in_months() {
+    let (months, unit) = Duration::from_earth_years(1.5).largest_unit();
+    assert_eq!("months", unit);
+    assert_in_delta(18.0, months);
+}
+
+#[test]
+fn multi_year_durations_are_given_in_years() {
+    let (years, unit) = Duration::from_earth_years(3.0).largest_unit();
+    assert_eq!("years", unit);
+    assert_in_delta(3.0, years);
+}
+
+#[test]
+fn mean_distance_lies_between_perihelion_and_aphelion() {
+    let (perihelion, aphelion) = (Earth::PERIHELION_KM, Earth::APHELION_KM);
+    assert!(perihelion < aphelion);
+    let mean = Earth::mean_distance_km();
+    assert!(perihelion < mean && mean < aphelion);
+    assert_eq!(149_600_000.0, mean);
+}
+
+#[test]
+fn total_years_across_durations() {
+    let durations = [
+        Duration::from(1_000_000_000),
+        Duration::from(2_000_000_000),
+        Duration::from(2_129_871_239),
+    ];
+    let expected = durations.iter().map(Mars::years_during).sum::<f64>();
+    assert_in_delta(expected, total_years::<Mars>(&durations));
+    assert_eq!(0.0, total_years::<Mars>(&[]));
+}
+
+#[test]
+fn whole_years_on_earth() {
+    let duration = Duration::from(2 * EARTH_YEAR_IN_SECONDS - 1);
+    assert_eq!(1, Earth::whole_years(&duration));
+    assert_eq!(
+        2,
+        Earth::whole_years(&Duration::from(2 * EARTH_YEAR_IN_SECONDS))
+    );
+}
+
+#[test]
+fn whole_years_on_neptune() {
+    assert_eq!(0, Neptune::whole_years(&Duration::from(2_129_871_239)));
+    let duration = Duration::from_earth_years(2.5 * Neptune::PERIOD);
+    assert_eq!(2, Neptune::whole_years(&duration));
+}
+
+#[test]
+fn planets_are_classified_by_type() {
+    assert_eq!(PlanetType::Terrestrial, Mars::KIND);
+    assert_eq!(PlanetType::GasGiant, Saturn::KIND);
+    assert_eq!(PlanetType::IceGiant, Neptune::KIND);
+    assert!(Jupiter::is_gas_giant());
+    assert!(!Uranus::is_gas_giant());
+    assert!(!Earth::is_gas_giant());
+}
+
+#[test]
+fn durations_from_years_on_a_planet() {
+    let duration = Duration::from_years_on::<Mars>(1.0);
+    assert_in_delta(1.0, Mars::years_during(&duration));
+    assert_in_delta(Mars::PERIOD, duration.as_earth_years());
+}
+
+#[test]
+fn young_people_had_birthdays_on_inner_planets_only() {
+    let duration = Duration::from_earth_years(0.5);
+    assert_eq!(vec!["Mercury"], planets_with_birthday(&duration));
+    assert!(planets_with_birthday(&Duration::ZERO).is_empty());
+}
+
+#[test]
+fn old_people_had_birthdays_everywhere() {
+    let duration = Duration::from_earth_years(200.0);
+    assert_eq!(8, planets_with_birthday(&duration).len());
+}
+
+#[test]
+fn approximate_comparisons() {
+    use std::cmp::Ordering;
+
+    let one = Duration::from_earth_years(1.0);
+    let two = Duration::from_earth_years(2.0);
+    let nearly_one = Duration::from_earth_years(1.0 + 1e-12);
+    assert_eq!(Ordering::Less, one.cmp_approx(&two, 1e-9));
+    assert_eq!(Ordering::Greater, two.cmp_approx(&one, 1e-9));
+    assert_eq!(Ordering::Equal, one.cmp_approx(&nearly_one, 1e-9));
+    assert_eq!(Ordering::Less, one.cmp_approx(&nearly_one, 1e-15));
+}
+
+#[test]
+fn leap_years_in_four_years() {
+    assert_eq!(1, leap_years_in(&Duration::from_earth_years(4.0)));
+    assert_eq!(0, leap_years_in(&Duration::from_earth_years(3.9)));
+}
+
+#[test]
+fn leap_years_in_a_century() {
+    assert_eq!(24, leap_years_in(&Duration::from_earth_years(100.0)));
+}
+
+#[test]
+fn leap_years_in_four_centuries() {
+    assert_eq!(97, leap_years_in(&Duration::from_earth_years(400.0)));
+}
+
+#[test]
+fn known_moons() {
+    assert_eq!(1, Earth::moons());
+    assert_eq!(95, Jupiter::moons());
+    assert_eq!(146, Saturn::moons());
+    let inner = [
+        Mercury::moons(),
+        Venus::moons(),
+        Earth::moons(),
+        Mars::moons(),
+    ];
+    assert!(inner
+        .iter()
+        .all(|&moons| moons < Jupiter::moons() && moons < Saturn::moons()));
+}
+
+#[test]
+fn ordering_agrees_with_equality_for_negative_zero_inputs() {
+    use std::cmp::Ordering;
+
+    for d in [Duration::ZERO * -1.0, Duration::from_minutes(-0.0)] {
+        assert_eq!(
+            d == Duration::ZERO,
+            d.partial_cmp(&Duration::ZERO) == Some(Ordering::Equal)
+        );
+    }
+}
+
+#[test]
+fn scaling_zero_by_a_negative_factor_yields_zero() {
+    assert_eq!(Duration::ZERO, Duration::ZERO * -1.0);
+    assert_eq!(Duration::ZERO, Duration::from(1_000) * -2.0);
+}
+
+#[test]
+fn negative_day_hour_and_minute_counts_are_clamped_to_zero() {
+    assert_eq!(Duration::ZERO, Duration::from_days(-1.0));
+    assert_eq!(Duration::ZERO, Duration::from_hours(-3.0));
+    assert_eq!(Duration::ZERO, Duration::from_minutes(-0.0));
+    assert_eq!("0 days", Duration::from_days(-1.0).human_readable());
+    assert_eq!("0.00 earth years", Duration::from_days(-1.0).to_string());
+}